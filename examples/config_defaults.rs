@@ -109,7 +109,7 @@ mod tests {
         
         assert_eq!(config.app_name, "My App");
         assert_eq!(config.port, 8080);
-        assert_eq!(config.debug_mode, false);
+        assert!(!config.debug_mode);
         assert_eq!(config.custom_header, None);
     }
 }
\ No newline at end of file
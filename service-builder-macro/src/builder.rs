@@ -1,12 +1,16 @@
 use proc_macro2::{TokenStream, Span};
 use quote::quote;
 use syn::{
-    Data, DeriveInput, Fields, Ident,
+    Data, DeriveInput, Fields, GenericArgument, Ident, PathArguments, Type,
 };
 
+use crate::delegate::delegate_macro_name;
 use crate::field_attributes::{FieldAttributes, DefaultValue};
+use crate::ordering::{referenced_fields, resolution_order};
+use crate::services::fields_macro_name;
+use crate::struct_attributes::StructAttributes;
 
-pub fn expand_builder(input: DeriveInput) -> syn::Result<TokenStream> {
+pub fn expand_builder(input: DeriveInput, struct_attrs: StructAttributes) -> syn::Result<TokenStream> {
     let struct_name = &input.ident;
     let builder_name = Ident::new(&format!("{}Builder", struct_name), Span::call_site());
     let vis = &input.vis;
@@ -21,106 +25,562 @@ pub fn expand_builder(input: DeriveInput) -> syn::Result<TokenStream> {
         _ => return Err(syn::Error::new(Span::call_site(), "Only structs are supported")),
     };
 
+    let field_names: Vec<&Ident> = fields.named.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+
     let mut field_defs = Vec::new();
     let mut builder_field_defs = Vec::new();
     let mut builder_new_fields = Vec::new();
     let mut builder_methods = Vec::new();
     let mut build_fields = Vec::new();
     let mut build_with_defaults_fields = Vec::new();
+    let mut field_dependencies = Vec::new();
+    let mut concurrent_factories = Vec::new();
+    let mut lifecycle_fields = Vec::new();
+    let mut health_fields = Vec::new();
+    let mut inject_fields = Vec::new();
+    let mut self_weak_fields = Vec::new();
+    let mut has_weak_fields = false;
+    let mut delegate_impls = Vec::new();
+    let mut service_fields = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut dependency_infos = Vec::new();
+    let mut graph_fields = Vec::new();
     let mut getters = Vec::new();
     let mut setters = Vec::new();
+    let mut update_seeds = Vec::new();
+    let mut update_carried = Vec::new();
+    let mut update_changes = Vec::new();
+    let mut update_notify = Vec::new();
 
     for field in fields.named.iter() {
         let field_name = field.ident.as_ref().unwrap();
-        let field_type = &field.ty;
-        let attrs = FieldAttributes::from_field(field, &input.attrs, &field.attrs);
+        let mut field_type = field.ty.clone();
+        let attrs = FieldAttributes::from_field(field, &input.attrs, &field.attrs)?;
+        let mut dependencies = Vec::new();
+        let mut concurrent_factory = None;
+
+        // Weak back-references to the struct itself are filled in by `build_cyclic`
+        let weak_target = if attrs.weak {
+            has_weak_fields = true;
+            let target = wrapped_type(&field_type, "Weak")
+                .ok_or_else(|| syn::Error::new_spanned(&field.ty, "weak fields must have type Weak<T>"))?;
+            if is_self_type(&target, struct_name) {
+                self_weak_fields.push(field_name);
+                field_type = syn::parse_quote! { std::sync::Weak<#struct_name #ty_generics> };
+                Some(syn::parse_quote! { #struct_name #ty_generics })
+            } else {
+                Some(target)
+            }
+        } else {
+            None
+        };
+        let field_type = &field_type;
+
+        // Lazy fields store their factory and build the value on first use
+        if attrs.lazy
+            && (attrs.factory.is_some() || attrs.optional || attrs.inject || attrs.inject_all || matches!(attrs.default, Some(DefaultValue::Expression(_))))
+        {
+            return Err(syn::Error::new(
+                field_name.span(),
+                "lazy fields cannot be combined with factory, optional, inject or default expressions",
+            ));
+        }
+        let builder_type = if attrs.lazy {
+            quote! { service_builder::lazy::Lazy<#field_type> }
+        } else {
+            quote! { #field_type }
+        };
+
+        // Swappable fields are resolved as the declared `Arc<T>` and moved into their cell when
+        // the struct is assembled, so factories and defaults still see the `Arc`
+        let swappable_target = if attrs.swappable {
+            if attrs.lazy || attrs.weak {
+                return Err(syn::Error::new(field_name.span(), "swappable cannot be used on lazy or weak fields"));
+            }
+            Some(wrapped_type(field_type, "Arc").ok_or_else(|| {
+                syn::Error::new_spanned(&field.ty, "swappable fields must have type Arc<T>")
+            })?)
+        } else {
+            None
+        };
+        let stored_type = match &swappable_target {
+            Some(target) => quote! { service_builder::swap::Swappable<#target> },
+            None => builder_type.clone(),
+        };
+        field_inits.push(if swappable_target.is_some() {
+            quote! { #field_name: service_builder::swap::Swappable::new(#field_name) }
+        } else {
+            quote! { #field_name }
+        });
 
         field_defs.push(quote! {
-            #field_name: #field_type
+            #field_name: #stored_type
         });
 
-        if attrs.builder {
+        // Observed fields keep their listeners in a hidden field next to them
+        let observers_name = Ident::new(&format!("__{}_observers", field_name), Span::call_site());
+        if attrs.observe {
+            if attrs.lazy || attrs.swappable {
+                return Err(syn::Error::new(field_name.span(), "observe cannot be used on lazy or swappable fields"));
+            }
+            field_defs.push(quote! {
+                #observers_name: service_builder::observe::Observers<#field_type>
+            });
+            field_inits.push(quote! {
+                #observers_name: service_builder::observe::Observers::new()
+            });
+            update_carried.push(observers_name.clone());
+        }
+
+        let has_builder_field = attrs.builder && (attrs.factory.is_none() || attrs.overridable);
+        // `update` seeds a builder with the current values of plain builder fields, recomputes
+        // factories and keeps the rest. A seeded value is marked as carried until the closure sets
+        // the field, so it is not checked, decorated or reported to listeners a second time
+        let carried_flag = (struct_attrs.update && attrs.builder && attrs.factory.is_none())
+            .then(|| Ident::new(&format!("__{}_carried", field_name), Span::call_site()));
+
+        // Builder fields are exported for `services!`, which sets them from services of the same type
+        if has_builder_field {
+            let kind = if attrs.lazy {
+                quote! { lazy }
+            } else if attrs.required {
+                quote! { required }
+            } else {
+                quote! { optional }
+            };
+            service_fields.push(quote! { #field_name: #field_type => #kind });
+        }
+        if struct_attrs.update {
+            if attrs.lazy {
+                return Err(syn::Error::new(field_name.span(), "update cannot be used on structs with lazy fields"));
+            }
+            if let Some(carried) = &carried_flag {
+                let current = if swappable_target.is_some() {
+                    quote! { self.#field_name.load() }
+                } else {
+                    quote! { std::clone::Clone::clone(&self.#field_name) }
+                };
+                update_seeds.push(quote! {
+                    builder.#field_name = Some(#current);
+                    builder.#carried = true;
+                });
+                if attrs.observe {
+                    let changed = Ident::new(&format!("__{}_changed", field_name), Span::call_site());
+                    update_changes.push(quote! { let #changed = !builder.#carried; });
+                    update_notify.push(quote! {
+                        if #changed {
+                            self.#observers_name.notify(&old.#field_name, &self.#field_name);
+                        }
+                    });
+                }
+            } else if attrs.factory.is_none() {
+                update_carried.push(field_name.clone());
+            }
+        }
+
+        if has_builder_field {
             builder_field_defs.push(quote! {
-                #field_name: std::option::Option<#field_type>
+                #field_name: std::option::Option<#builder_type>
             });
 
             builder_new_fields.push(quote! {
                 #field_name: None
             });
 
-            builder_methods.push(quote! {
-                pub fn #field_name(mut self, value: #field_type) -> Self {
-                    self.#field_name = Some(value);
-                    self
+            if let Some(carried) = &carried_flag {
+                builder_field_defs.push(quote! { #carried: bool });
+                builder_new_fields.push(quote! { #carried: false });
+            }
+
+            if attrs.lazy {
+                let try_method = Ident::new(&format!("try_{}", field_name), Span::call_site());
+                builder_methods.push(quote! {
+                    /// Sets the factory that creates this field on first use.
+                    pub fn #field_name<__F>(mut self, factory: __F) -> Self
+                    where
+                        __F: Fn() -> #field_type + Send + Sync + 'static,
+                    {
+                        self.#field_name = Some(service_builder::lazy::Lazy::new(move || Ok(factory())));
+                        self
+                    }
+
+                    /// Sets a fallible factory that creates this field on first use.
+                    pub fn #try_method<__F, __E>(mut self, factory: __F) -> Self
+                    where
+                        __F: Fn() -> Result<#field_type, __E> + Send + Sync + 'static,
+                        __E: std::fmt::Display,
+                    {
+                        self.#field_name = Some(service_builder::lazy::Lazy::new(move || {
+                            factory().map_err(|err| service_builder::error::BuildError::InitializationError(
+                                format!("{}.{}: {}", stringify!(#struct_name), stringify!(#field_name), err)
+                            ))
+                        }));
+                        self
+                    }
+                });
+            } else {
+                let clear_carried = carried_flag.as_ref().map(|carried| quote! { self.#carried = false; });
+                builder_methods.push(quote! {
+                    pub fn #field_name(mut self, value: #field_type) -> Self {
+                        self.#field_name = Some(value);
+                        #clear_carried
+                        self
+                    }
+                });
+            }
+
+            if attrs.inject {
+                let name = match &attrs.inject_name {
+                    Some(name) => quote! { Some(#name) },
+                    None => quote! { None },
+                };
+                inject_fields.push(quote! {
+                    if self.#field_name.is_none() {
+                        match <#field_type as service_builder::registry::Injectable>::resolve(registry, #name) {
+                            Ok(value) => self.#field_name = Some(value),
+                            Err(service_builder::error::BuildError::MissingDependency(service)) => missing.push(service),
+                            Err(err) => return Err(err),
+                        }
+                    }
+                });
+            } else if attrs.inject_all {
+                inject_fields.push(quote! {
+                    if self.#field_name.is_none() {
+                        self.#field_name = Some(<#field_type as service_builder::registry::InjectAll>::resolve_all(registry));
+                    }
+                });
+            }
+        }
+
+        if !attrs.builder {
+            build_fields.push(quote! { Default::default() });
+            build_with_defaults_fields.push(quote! { Default::default() });
+        } else if let Some(factory) = &attrs.factory {
+            // Factories may refer to other fields by name; they are evaluated once those are resolved
+            let factory_tokens: TokenStream = factory.parse().unwrap_or_else(|_| quote! { compile_error!("Invalid factory expression") });
+            dependencies = referenced_fields(&factory_tokens, &field_names);
+            let factory_call = if attrs.async_factory {
+                if !struct_attrs.is_async {
+                    return Err(syn::Error::new(field_name.span(), "async_factory requires #[builder(async)] on the struct"));
                 }
-            });
+                quote! { (#factory_tokens).await }
+            } else {
+                quote! { (#factory_tokens) }
+            };
+            let run_factory = quote! {
+                #factory_call.map_err(|err| service_builder::error::BuildError::InitializationError(
+                    format!("{}.{}: {}", stringify!(#struct_name), stringify!(#field_name), err)
+                ))
+            };
+            let value = if attrs.overridable {
+                quote! {
+                    match self.#field_name {
+                        Some(value) => value,
+                        None => #run_factory?,
+                    }
+                }
+            } else {
+                quote! { #run_factory? }
+            };
+            build_fields.push(value.clone());
+            build_with_defaults_fields.push(value);
+
+            if attrs.async_factory {
+                // Concurrent resolution takes any builder value out of `self` before awaiting
+                let override_name = Ident::new(&format!("__{}_override", field_name), Span::call_site());
+                concurrent_factory = Some(ConcurrentFactory {
+                    take_override: attrs.overridable.then(|| quote! { let #override_name = self.#field_name; }),
+                    result: if attrs.overridable {
+                        quote! {
+                            match #override_name {
+                                Some(value) => Ok(value),
+                                None => #run_factory,
+                            }
+                        }
+                    } else {
+                        run_factory
+                    },
+                });
+            }
+        } else {
+            // Default expressions may refer to other fields by name, like factories
+            if let Some(DefaultValue::Expression(expr)) = &attrs.default {
+                if let Ok(expr_tokens) = expr.parse::<TokenStream>() {
+                    dependencies = referenced_fields(&expr_tokens, &field_names);
+                }
+            }
 
             // For strict build() method
             if attrs.required {
                 build_fields.push(quote! {
-                    #field_name: self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
+                    self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
                 });
             } else if let Some(default_value) = &attrs.default {
                 match default_value {
                     DefaultValue::Default => {
                         build_fields.push(quote! {
-                            #field_name: self.#field_name.unwrap_or_default()
+                            self.#field_name.unwrap_or_default()
                         });
                     }
                     DefaultValue::Expression(expr) => {
                         let expr_tokens: TokenStream = expr.parse().unwrap_or_else(|_| quote! { compile_error!("Invalid default expression") });
                         build_fields.push(quote! {
-                            #field_name: self.#field_name.unwrap_or_else(|| #expr_tokens)
+                            self.#field_name.unwrap_or_else(|| #expr_tokens)
                         });
                     }
                 }
             } else if attrs.optional {
                 // For optional fields without explicit default, use None for Option<T> types
                 build_fields.push(quote! {
-                    #field_name: self.#field_name.unwrap_or(None)
+                    self.#field_name.unwrap_or(None)
                 });
             } else {
                 // No default specified and not marked as optional - this field is still required
                 build_fields.push(quote! {
-                    #field_name: self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
+                    self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
                 });
             }
-            
+
             // For build_with_defaults() method - always provide a value
             if let Some(default_value) = &attrs.default {
                 match default_value {
                     DefaultValue::Default => {
                         build_with_defaults_fields.push(quote! {
-                            #field_name: self.#field_name.unwrap_or_default()
+                            self.#field_name.unwrap_or_default()
                         });
                     }
                     DefaultValue::Expression(expr) => {
                         let expr_tokens: TokenStream = expr.parse().unwrap_or_else(|_| quote! { compile_error!("Invalid default expression") });
                         build_with_defaults_fields.push(quote! {
-                            #field_name: self.#field_name.unwrap_or_else(|| #expr_tokens)
+                            self.#field_name.unwrap_or_else(|| #expr_tokens)
                         });
                     }
                 }
             } else if attrs.optional {
                 build_with_defaults_fields.push(quote! {
-                    #field_name: self.#field_name.unwrap_or(None)
+                    self.#field_name.unwrap_or(None)
                 });
             } else {
                 // For fields without explicit default, they are still required even in build_with_defaults
                 build_with_defaults_fields.push(quote! {
-                    #field_name: self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
+                    self.#field_name.ok_or_else(|| service_builder::error::BuildError::MissingDependency(stringify!(#field_name).to_string()))?
                 });
             }
+        }
+
+        // Setter checks also validate the value the struct is built with
+        let setter_check = match &attrs.setter_check {
+            Some(check) => Some(
+                check
+                    .parse::<TokenStream>()
+                    .map_err(|_| syn::Error::new(field_name.span(), "Invalid setter check expression"))?,
+            ),
+            None => None,
+        };
+        let check_error = quote! {
+            |err| service_builder::error::BuildError::ConfigurationError(
+                format!("{}.{}: {}", stringify!(#struct_name), stringify!(#field_name), err)
+            )
+        };
+        // Skipped, lazy and weak fields are not set by the builder, so only their setters check
+        if let Some(check) = setter_check.as_ref().filter(|_| attrs.builder && !attrs.lazy && !attrs.weak) {
+            let run_check = quote! { (#check)(&__value).map_err(#check_error)?; };
+            let run_check = match &carried_flag {
+                Some(carried) => quote! {
+                    if !self.#carried {
+                        #run_check
+                    }
+                },
+                None => run_check,
+            };
+            for values in [&mut build_fields, &mut build_with_defaults_fields] {
+                let value = values.pop().unwrap();
+                values.push(quote! {
+                    {
+                        let __value = #value;
+                        #run_check
+                        __value
+                    }
+                });
+            }
+            if let Some(factory) = &mut concurrent_factory {
+                let result = &factory.result;
+                factory.result = quote! {
+                    (#result).and_then(|__value| (#check)(&__value).map(|_| __value).map_err(#check_error))
+                };
+            }
+        }
+
+        // Test overrides replace `Arc` services before the builder's value, default or factory is used,
+        // matching the field's inject name if it has one. Fields of generic structs are left alone,
+        // as their types may not be `'static`. Without the `test-overrides` feature no lookup is emitted
+        if cfg!(feature = "test-overrides") && attrs.builder && !attrs.lazy && generics.params.is_empty() {
+            if let Some(target) = wrapped_type(field_type, "Arc") {
+                let name = match attrs.inject_name.as_ref().filter(|_| attrs.inject) {
+                    Some(name) => quote! { Some(#name) },
+                    None => quote! { None },
+                };
+                for values in [&mut build_fields, &mut build_with_defaults_fields] {
+                    let value = values.pop().unwrap();
+                    values.push(quote! {
+                        match service_builder::overrides::lookup::<#target>(#name) {
+                            Some(service) => service,
+                            None => #value,
+                        }
+                    });
+                }
+                if let Some(factory) = &mut concurrent_factory {
+                    let result = &factory.result;
+                    factory.result = quote! {
+                        match service_builder::overrides::lookup::<#target>(#name) {
+                            Some(service) => Ok(service),
+                            None => #result,
+                        }
+                    };
+                }
+            }
+        }
+
+        // Decorators wrap the resolved value, the first one declared innermost
+        if !attrs.decorators.is_empty() {
+            if attrs.lazy || attrs.weak {
+                return Err(syn::Error::new(field_name.span(), "decorate cannot be used on lazy or weak fields"));
+            }
+            let decorators = attrs
+                .decorators
+                .iter()
+                .map(|decorator| decorator.parse::<TokenStream>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| syn::Error::new(field_name.span(), "Invalid decorate expression"))?;
+            let decorate = |value: TokenStream| {
+                decorators.iter().fold(value, |value, decorator| quote! { (#decorator)(#value) })
+            };
+            for values in [&mut build_fields, &mut build_with_defaults_fields] {
+                let value = values.pop().unwrap();
+                values.push(match &carried_flag {
+                    Some(carried) => {
+                        let decorated = decorate(quote! { __value });
+                        quote! {
+                            {
+                                let __value = #value;
+                                if self.#carried {
+                                    __value
+                                } else {
+                                    #decorated
+                                }
+                            }
+                        }
+                    }
+                    None => decorate(value),
+                });
+            }
+            if let Some(factory) = &mut concurrent_factory {
+                let decorated = decorate(quote! { service });
+                let result = &factory.result;
+                factory.result = quote! { (#result).map(|service| #decorated) };
+            }
+        }
+
+        // Delegated traits are implemented by the macro `#[delegatable]` generates next to the trait
+        for delegation in &attrs.delegates {
+            let trait_path = &delegation.trait_path;
+            let except = delegation.except.iter().map(|(name, target)| quote! { #name = #target });
+            let mut macro_path = trait_path.clone();
+            let last = macro_path.segments.last_mut().unwrap();
+            last.ident = delegate_macro_name(&last.ident);
+            delegate_impls.push(quote! {
+                #macro_path! {
+                    { #trait_path }
+                    { impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause }
+                    #field_name [#(#except),*]
+                }
+            });
+        }
+
+        // Describe how the builder sets the field, for `dependency_info()`
+        let kind = if !attrs.builder {
+            quote! { Skipped }
+        } else if attrs.factory.is_some() {
+            quote! { Factory }
+        } else if attrs.lazy {
+            quote! { Lazy }
+        } else if attrs.weak {
+            quote! { Weak }
+        } else if attrs.inject || attrs.inject_all {
+            quote! { Injected }
+        } else if attrs.optional {
+            quote! { Optional }
+        } else if attrs.default.is_some() {
+            quote! { Defaulted }
         } else {
-            build_fields.push(quote! {
-                #field_name: Default::default()
+            quote! { Required }
+        };
+        let type_name = type_string(&field.ty);
+        dependency_infos.push(quote! {
+            service_builder::graph::Dependency {
+                field: stringify!(#field_name),
+                type_name: #type_name,
+                kind: service_builder::graph::DependencyKind::#kind,
+            }
+        });
+        if attrs.graph {
+            graph_fields.push(field_name);
+        }
+
+        field_dependencies.push(dependencies);
+        lifecycle_fields.push(attrs.lifecycle);
+        if attrs.health {
+            health_fields.push(field_name);
+        }
+        concurrent_factories.push(concurrent_factory);
+
+        if let Some(target) = &weak_target {
+            // Weak fields always get getters, which upgrade the reference
+            let getter_name = Ident::new(&format!("get_{}", field_name), Span::call_site());
+            getters.push(quote! {
+                /// Returns the referenced service, or `None` if it has been dropped or was never set.
+                pub fn #getter_name(&self) -> Option<std::sync::Arc<#target>> {
+                    self.#field_name.upgrade()
+                }
             });
-            build_with_defaults_fields.push(quote! {
-                #field_name: Default::default()
+        } else if let Some(target) = &swappable_target {
+            // Swappable fields are read and replaced through `&self`
+            let load_name = Ident::new(&format!("load_{}", field_name), Span::call_site());
+            let swap_name = Ident::new(&format!("swap_{}", field_name), Span::call_site());
+            getters.push(quote! {
+                /// Returns the current service.
+                pub fn #load_name(&self) -> std::sync::Arc<#target> {
+                    self.#field_name.load()
+                }
+
+                /// Replaces the service for later calls, returning the previous one.
+                pub fn #swap_name(&self, service: std::sync::Arc<#target>) -> std::sync::Arc<#target> {
+                    self.#field_name.swap(service)
+                }
             });
-        }
+        } else if attrs.lazy {
+            // Lazy fields always get getters, which run the factory on first use
+            let getter_name = Ident::new(&format!("get_{}", field_name), Span::call_site());
+            let try_getter_name = Ident::new(&format!("try_get_{}", field_name), Span::call_site());
+            getters.push(quote! {
+                /// Returns the value, creating it on first use.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the factory fails; use the `try_get_` getter to handle the error.
+                pub fn #getter_name(&self) -> &#field_type {
+                    match self.#field_name.get() {
+                        Ok(value) => value,
+                        Err(err) => panic!("{}", err),
+                    }
+                }
 
-        if attrs.getter {
+                /// Returns the value, creating it on first use, or the factory's error.
+                pub fn #try_getter_name(&self) -> Result<&#field_type, service_builder::error::BuildError> {
+                    self.#field_name.get()
+                }
+            });
+        } else if attrs.getter {
             let getter_name = Ident::new(&format!("get_{}", field_name), Span::call_site());
             getters.push(quote! {
                 pub fn #getter_name(&self) -> &#field_type {
@@ -129,54 +589,651 @@ pub fn expand_builder(input: DeriveInput) -> syn::Result<TokenStream> {
             });
         }
 
-        if attrs.setter {
-            let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
+        if attrs.observe {
+            // Observed fields always get setters, which notify listeners after the update
+            let subscribe_name = Ident::new(&format!("subscribe_{}", field_name), Span::call_site());
+            let unsubscribe_name = Ident::new(&format!("unsubscribe_{}", field_name), Span::call_site());
             setters.push(quote! {
-                pub fn #setter_name(&mut self, value: #field_type) {
-                    self.#field_name = value;
+                /// Calls `listener` with the old and new value whenever the field is set.
+                pub fn #subscribe_name<__F>(&self, listener: __F) -> service_builder::observe::Subscription
+                where
+                    __F: Fn(&#field_type, &#field_type) + Send + Sync + 'static,
+                {
+                    self.#observers_name.subscribe(listener)
+                }
+
+                /// Removes a listener, returning `false` if it was not subscribed.
+                pub fn #unsubscribe_name(&self, subscription: service_builder::observe::Subscription) -> bool {
+                    self.#observers_name.unsubscribe(subscription)
+                }
+            });
+        }
+
+        if attrs.setter || attrs.observe {
+            let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
+            let value = if attrs.lazy {
+                quote! { service_builder::lazy::Lazy::with_value(value) }
+            } else if swappable_target.is_some() {
+                quote! { service_builder::swap::Swappable::new(value) }
+            } else {
+                quote! { value }
+            };
+            let check = match &setter_check {
+                Some(check) => quote! {
+                    (#check)(&value).map_err(#check_error)?;
+                },
+                None => quote! {},
+            };
+            // The invariant sees the struct with the new value, which is put back on failure
+            let invariant = struct_attrs.invariant.as_ref().map(|_| {
+                quote! {
+                    if let Err(err) = self.__check_invariant() {
+                        self.#field_name = old;
+                        return Err(err);
+                    }
                 }
             });
+            let notify = attrs.observe.then(|| quote! { self.#observers_name.notify(&old, &self.#field_name); });
+            let assign = if invariant.is_some() || notify.is_some() {
+                quote! { let old = std::mem::replace(&mut self.#field_name, #value); }
+            } else {
+                quote! { self.#field_name = #value; }
+            };
+
+            if attrs.setter_check.is_some() || invariant.is_some() {
+                setters.push(quote! {
+                    /// Sets the field if the new value passes validation, leaving the struct unchanged otherwise.
+                    pub fn #setter_name(&mut self, value: #field_type) -> Result<(), service_builder::error::BuildError> {
+                        #check
+                        #assign
+                        #invariant
+                        #notify
+                        Ok(())
+                    }
+                });
+            } else {
+                setters.push(quote! {
+                    pub fn #setter_name(&mut self, value: #field_type) {
+                        #assign
+                        #notify
+                    }
+                });
+            }
         }
     }
 
+    // Resolve fields in dependency order, keeping declaration order where possible
+    let dependency_indices: Vec<Vec<usize>> = field_dependencies
+        .iter()
+        .enumerate()
+        .map(|(index, dependencies)| {
+            dependencies
+                .iter()
+                .map(|dependency| field_names.iter().position(|name| *name == dependency).unwrap())
+                .filter(|&dependency| dependency != index)
+                .collect()
+        })
+        .collect();
+    let order = resolution_order(&dependency_indices).map_err(|cycle| {
+        let path: Vec<String> = cycle.iter().map(|&index| field_names[index].to_string()).collect();
+        syn::Error::new(
+            field_names[cycle[0]].span(),
+            format!("circular field dependency: {}", path.join(" -> ")),
+        )
+    })?;
+    if struct_attrs.concurrent && !struct_attrs.is_async {
+        return Err(syn::Error::new(Span::call_site(), "concurrent requires #[builder(async)]"));
+    }
+    let resolve_build_fields = resolve_fields(&order, &dependency_indices, &field_names, &build_fields, &concurrent_factories, struct_attrs.concurrent);
+    let resolve_build_with_defaults_fields = resolve_fields(&order, &dependency_indices, &field_names, &build_with_defaults_fields, &concurrent_factories, struct_attrs.concurrent);
+
+    // Lifecycle components start in resolution order and stop in reverse
+    let lifecycle_impl = if struct_attrs.lifecycle {
+        let components: Vec<&Ident> = order
+            .iter()
+            .filter(|&&index| lifecycle_fields[index])
+            .map(|&index| field_names[index])
+            .collect();
+        let component_list = quote! {
+            &[#((stringify!(#components), &self.#components as &dyn service_builder::lifecycle::Lifecycle)),*]
+        };
+        quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                pub fn start_all(&self) -> Result<(), service_builder::error::BuildError> {
+                    service_builder::lifecycle::start_all(#component_list)
+                }
+
+                pub fn stop_all(&self) -> Result<(), service_builder::error::BuildError> {
+                    service_builder::lifecycle::stop_all(#component_list)
+                }
+            }
+
+            impl #impl_generics service_builder::lifecycle::Lifecycle for #struct_name #ty_generics #where_clause {
+                fn start(&self) -> service_builder::lifecycle::LifecycleResult {
+                    self.start_all().map_err(Into::into)
+                }
+
+                fn stop(&self) -> service_builder::lifecycle::LifecycleResult {
+                    self.stop_all().map_err(Into::into)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Health reports list the checked fields in declaration order
+    let health_impl = if struct_attrs.health {
+        quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                pub fn health(&self) -> service_builder::health::Health {
+                    service_builder::health::Health::aggregate(vec![
+                        #(service_builder::health::check(stringify!(#health_fields), &self.#health_fields)),*
+                    ])
+                }
+            }
+
+            impl #impl_generics service_builder::health::HealthCheck for #struct_name #ty_generics #where_clause {
+                fn check_health(&self) -> service_builder::health::Health {
+                    self.health()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Scoped services tell the container how long their instances are reused
+    // `services!` cannot see the struct's fields, so they are handed to it through a callback
+    let fields_macro = fields_macro_name(struct_name);
+    let fields_macro_impl = quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #fields_macro {
+            ($($args:tt)*) => {
+                service_builder::__services_impl! { $($args)* { #(#service_fields),* } }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #fields_macro;
+    };
+
+    let scope_impl = match &struct_attrs.scope {
+        Some(scope) => quote! {
+            impl #impl_generics service_builder::container::Scoped for #struct_name #ty_generics #where_clause {
+                const SCOPE: service_builder::container::Scope = service_builder::container::Scope::#scope;
+            }
+        },
+        None => quote! {},
+    };
+
+    // Async builders resolve fields in an `async fn` and return its future from `build()`
+    let (asyncness, await_future) = if struct_attrs.is_async {
+        (quote! { async }, quote! { .await })
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // Post-build hook, run on the assembled struct before it is returned. The hook is
+    // called from the struct's own impl block so that `Self` refers to the struct.
+    // In async builders the hook returns a future, which is awaited.
+    let (init_hook, init_fn) = match &struct_attrs.init {
+        Some(hook) => (
+            quote! {
+                #struct_name::__init(service)#await_future?;
+            },
+            quote! {
+                #asyncness fn __init(service: &mut Self) -> Result<(), service_builder::error::BuildError> {
+                    #hook(service)#await_future.map_err(|err| service_builder::error::BuildError::InitializationError(
+                        format!("{}: {}", stringify!(#struct_name), err)
+                    ))
+                }
+            },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
+    // Invariant over the whole struct, checked after the init hook and by validated setters
+    let (invariant_check, invariant_fn) = match &struct_attrs.invariant {
+        Some(invariant) => (
+            quote! {
+                service.__check_invariant()?;
+            },
+            quote! {
+                fn __check_invariant(&self) -> Result<(), service_builder::error::BuildError> {
+                    #invariant(self).map_err(|err| service_builder::error::BuildError::ConfigurationError(
+                        format!("{}: {}", stringify!(#struct_name), err)
+                    ))
+                }
+            },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+    let build_output = if struct_attrs.is_async {
+        quote! { impl std::future::Future<Output = Result<#struct_name #ty_generics, service_builder::error::BuildError>> }
+    } else {
+        quote! { Result<#struct_name #ty_generics, service_builder::error::BuildError> }
+    };
+    let locate = |result: TokenStream| {
+        let located = quote! { (#result).map_err(|err| err.located(created_at, built_at)) };
+        if struct_attrs.is_async {
+            quote! { async move { #located } }
+        } else {
+            located
+        }
+    };
+    let located_build = locate(quote! { self.__build()#await_future });
+    let located_build_with_defaults = locate(quote! { self.__build_with_defaults()#await_future });
+
+    // Global services live in a static slot, so the struct cannot be generic
+    let global_impl = if struct_attrs.global {
+        if !generics.params.is_empty() {
+            return Err(syn::Error::new(Span::call_site(), "global cannot be used on generic structs"));
+        }
+        let track_caller = if struct_attrs.is_async { quote! {} } else { quote! { #[track_caller] } };
+        quote! {
+            impl #struct_name {
+                fn __global_slot() -> &'static service_builder::global::GlobalSlot<Self> {
+                    static SLOT: service_builder::global::GlobalSlot<#struct_name> = service_builder::global::GlobalSlot::new();
+                    &SLOT
+                }
+
+                /// Builds the process-wide instance returned by `global()`.
+                ///
+                /// Fails with `BuildError::ConfigurationError` if it is already initialized.
+                #track_caller
+                pub #asyncness fn init_global(builder: #builder_name) -> Result<(), service_builder::error::BuildError> {
+                    Self::__global_slot().ensure_uninitialized(stringify!(#struct_name))?;
+                    let service = builder.build()#await_future?;
+                    Self::__global_slot().init(service, stringify!(#struct_name))
+                }
+
+                /// Returns the process-wide instance.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `init_global()` has not been called.
+                pub fn global() -> &'static Self {
+                    match Self::__global_slot().get() {
+                        Some(service) => service,
+                        None => panic!(
+                            "{}::global() called before {}::init_global()",
+                            stringify!(#struct_name),
+                            stringify!(#struct_name)
+                        ),
+                    }
+                }
+
+                /// Returns the process-wide instance, or `None` if it is not initialized.
+                pub fn try_global() -> Option<&'static Self> {
+                    Self::__global_slot().get()
+                }
+
+                /// Makes `global()` return `service` until the guard is dropped, then restores the
+                /// previous instance.
+                #[cfg(test)]
+                pub fn override_global(service: Self) -> service_builder::global::GlobalOverride<Self> {
+                    Self::__global_slot().replace(service)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Updates go through a full build, so factories, the init hook and the invariant run again.
+    // Skipped fields and listeners are swapped into the new struct before the init hook and
+    // invariant see it, and swapped back if either fails
+    let update_fn = if struct_attrs.update {
+        let track_caller = if struct_attrs.is_async { quote! {} } else { quote! { #[track_caller] } };
+        let swap_carried = quote! {
+            #(std::mem::swap(&mut service.#update_carried, &mut self.#update_carried);)*
+        };
+        quote! {
+            /// Applies `update` to a builder holding the current values, then replaces the struct with
+            /// the result. If building fails, the error is returned and the struct is left unchanged.
+            ///
+            /// Skipped fields keep their values, and factory fields are computed again. Field checks,
+            /// decorators and listeners only see the fields `update` sets.
+            #track_caller
+            pub #asyncness fn update<__F>(&mut self, update: __F) -> Result<(), service_builder::error::BuildError>
+            where
+                __F: FnOnce(#builder_name #ty_generics) -> #builder_name #ty_generics,
+            {
+                let built_at = std::panic::Location::caller();
+                #[allow(unused_mut)]
+                let mut builder = #builder_name::new();
+                #(#update_seeds)*
+                let builder = update(builder);
+                #(#update_changes)*
+                let created_at = builder.__created_at;
+                let mut service = match builder.__assemble()#await_future {
+                    Ok(service) => service,
+                    Err(err) => return Err(err.located(created_at, built_at)),
+                };
+                #swap_carried
+                if let Err(err) = #builder_name::__finish(&mut service)#await_future {
+                    #swap_carried
+                    return Err(err.located(created_at, built_at));
+                }
+                #[allow(unused_variables)]
+                let old = std::mem::replace(self, service);
+                #(#update_notify)*
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Cyclic builds resolve every field first, so `Arc::new_cyclic` only has infallible work left
+    let cyclic_output = if struct_attrs.is_async {
+        quote! { impl std::future::Future<Output = Result<std::sync::Arc<#struct_name #ty_generics>, service_builder::error::BuildError>> }
+    } else {
+        quote! { Result<std::sync::Arc<#struct_name #ty_generics>, service_builder::error::BuildError> }
+    };
+    let cyclic = |wire: TokenStream| {
+        locate(quote! {
+            self.__build()#await_future.map(|mut service| {
+                std::sync::Arc::new_cyclic(move |this| {
+                    #(service.#self_weak_fields = this.clone();)*
+                    #wire
+                    service
+                })
+            })
+        })
+    };
+    let cyclic_fns = if has_weak_fields {
+        let located_build_cyclic = cyclic(quote! {});
+        let located_build_cyclic_with = cyclic(quote! { wire(&mut service, this); });
+        quote! {
+            /// Builds the service into an `Arc`, then sets its `Weak<Self>` fields to point at it.
+            #[track_caller]
+            pub fn build_cyclic(self) -> #cyclic_output {
+                let created_at = self.__created_at;
+                let built_at = std::panic::Location::caller();
+                #located_build_cyclic
+            }
+
+            /// Like [`Self::build_cyclic`], and also passes the service and a `Weak` to it to `wire`
+            /// before it is placed in the `Arc`, e.g. to hand the `Weak` to a sibling service.
+            #[track_caller]
+            pub fn build_cyclic_with<__F>(self, wire: __F) -> #cyclic_output
+            where
+                __F: FnOnce(&mut #struct_name #ty_generics, &std::sync::Weak<#struct_name #ty_generics>),
+            {
+                let created_at = self.__created_at;
+                let built_at = std::panic::Location::caller();
+                #located_build_cyclic_with
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Injected fields are resolved from a registry before the regular build
+    let inject_fns = if inject_fields.is_empty() {
+        quote! {}
+    } else {
+        let located_build_from = locate(quote! {
+            match filled {
+                Ok(builder) => builder.__build()#await_future,
+                Err(err) => Err(err),
+            }
+        });
+        quote! {
+            /// Sets every unset `#[builder(inject)]` and `#[builder(inject_all)]` field from `registry`.
+            ///
+            /// Fails with `BuildError::MissingDependency` listing every service that could not be resolved.
+            pub fn fill_from(mut self, registry: &service_builder::registry::ServiceRegistry) -> Result<Self, service_builder::error::BuildError> {
+                let mut missing: Vec<String> = Vec::new();
+                #(#inject_fields)*
+                if missing.is_empty() {
+                    Ok(self)
+                } else {
+                    Err(service_builder::error::BuildError::MissingDependency(missing.join(", ")))
+                }
+            }
+
+            /// Fills `#[builder(inject)]` fields from `registry` with [`Self::fill_from`], then builds.
+            #[track_caller]
+            pub fn build_from(self, registry: &service_builder::registry::ServiceRegistry) -> #build_output {
+                let created_at = self.__created_at;
+                let built_at = std::panic::Location::caller();
+                let filled = self.fill_from(registry);
+                #located_build_from
+            }
+        }
+    };
+
     Ok(quote! {
         #vis struct #struct_name #ty_generics #where_clause {
             #(#field_defs),*
         }
 
         #vis struct #builder_name #ty_generics #where_clause {
-            #(#builder_field_defs),*
+            #(#builder_field_defs,)*
+            __created_at: &'static std::panic::Location<'static>
         }
 
         impl #impl_generics #builder_name #ty_generics #where_clause {
+            #[track_caller]
             pub fn new() -> Self {
                 Self {
-                    #(#builder_new_fields),*
+                    #(#builder_new_fields,)*
+                    __created_at: std::panic::Location::caller()
                 }
             }
 
             #(#builder_methods)*
 
-            pub fn build(self) -> Result<#struct_name #ty_generics, service_builder::error::BuildError> {
-                Ok(#struct_name {
-                    #(#build_fields),*
-                })
+            #[track_caller]
+            pub fn build(self) -> #build_output {
+                let created_at = self.__created_at;
+                let built_at = std::panic::Location::caller();
+                #located_build
+            }
+
+            #[track_caller]
+            pub fn build_with_defaults(self) -> #build_output {
+                let created_at = self.__created_at;
+                let built_at = std::panic::Location::caller();
+                #located_build_with_defaults
+            }
+
+            #cyclic_fns
+
+            #inject_fns
+
+            #asyncness fn __build(self) -> Result<#struct_name #ty_generics, service_builder::error::BuildError> {
+                let mut service = self.__assemble()#await_future?;
+                Self::__finish(&mut service)#await_future?;
+                Ok(service)
             }
-            
-            pub fn build_with_defaults(self) -> Result<#struct_name #ty_generics, service_builder::error::BuildError> {
+
+            #asyncness fn __build_with_defaults(self) -> Result<#struct_name #ty_generics, service_builder::error::BuildError> {
+                #resolve_build_with_defaults_fields
+                let mut service = #struct_name {
+                    #(#field_inits),*
+                };
+                Self::__finish(&mut service)#await_future?;
+                Ok(service)
+            }
+
+            /// Resolves every field and assembles the struct, without running the init hook or invariant.
+            #asyncness fn __assemble(self) -> Result<#struct_name #ty_generics, service_builder::error::BuildError> {
+                #resolve_build_fields
                 Ok(#struct_name {
-                    #(#build_with_defaults_fields),*
+                    #(#field_inits),*
                 })
             }
+
+            /// Runs the init hook and invariant on an assembled struct.
+            #asyncness fn __finish(service: &mut #struct_name #ty_generics) -> Result<(), service_builder::error::BuildError> {
+                #init_hook
+                #invariant_check
+                Ok(())
+            }
         }
 
         impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[track_caller]
             pub fn builder() -> #builder_name #ty_generics {
                 #builder_name::new()
             }
 
+            #init_fn
+
+            #invariant_fn
+
+            /// Lists the struct's fields and how the builder sets them.
+            pub fn dependency_info() -> &'static [service_builder::graph::Dependency] {
+                &[#(#dependency_infos),*]
+            }
+
             #(#getters)*
             #(#setters)*
+
+            #update_fn
+        }
+
+        impl #impl_generics service_builder::graph::DependencyInfo for #struct_name #ty_generics #where_clause {
+            fn graph(&self) -> service_builder::graph::DependencyGraph {
+                service_builder::graph::DependencyGraph::new(
+                    stringify!(#struct_name),
+                    Self::dependency_info(),
+                    vec![#((stringify!(#graph_fields), service_builder::graph::DependencyInfo::graph(&self.#graph_fields))),*],
+                )
+            }
         }
+
+        #(#delegate_impls)*
+        #fields_macro_impl
+        #global_impl
+        #lifecycle_impl
+        #health_impl
+        #scope_impl
     })
-}
\ No newline at end of file
+}
+
+/// An async factory that can be awaited together with other factories.
+struct ConcurrentFactory {
+    /// Moves a builder-provided value out of `self` before the factories are awaited
+    take_override: Option<TokenStream>,
+    /// Evaluates to the field's `Result<T, BuildError>`
+    result: TokenStream,
+}
+
+/// Emits the `let` statements resolving every field, following `order`.
+///
+/// With `concurrent`, fields are grouped into levels that only depend on earlier levels,
+/// and async factories in the same level are awaited together through
+/// nested `service_builder::future::join` calls.
+fn resolve_fields(
+    order: &[usize],
+    dependencies: &[Vec<usize>],
+    field_names: &[&Ident],
+    values: &[TokenStream],
+    concurrent_factories: &[Option<ConcurrentFactory>],
+    concurrent: bool,
+) -> TokenStream {
+    if !concurrent {
+        let statements = order.iter().map(|&index| {
+            let name = field_names[index];
+            let value = &values[index];
+            quote! { let #name = #value; }
+        });
+        return quote! { #(#statements)* };
+    }
+
+    let mut levels = vec![0; order.len()];
+    for &index in order {
+        levels[index] = dependencies[index].iter().map(|&dep| levels[dep] + 1).max().unwrap_or(0);
+    }
+    let max_level = levels.iter().copied().max().unwrap_or(0);
+
+    let mut statements = Vec::new();
+    for level in 0..=max_level {
+        let in_level: Vec<usize> = order.iter().copied().filter(|&index| levels[index] == level).collect();
+        let joined: Vec<usize> = in_level.iter().copied().filter(|&index| concurrent_factories[index].is_some()).collect();
+
+        for &index in &in_level {
+            if joined.len() < 2 || !joined.contains(&index) {
+                let name = field_names[index];
+                let value = &values[index];
+                statements.push(quote! { let #name = #value; });
+            }
+        }
+
+        if joined.len() >= 2 {
+            let names: Vec<&Ident> = joined.iter().map(|&index| field_names[index]).collect();
+            let slots: Vec<Ident> = names
+                .iter()
+                .map(|name| Ident::new(&format!("__{}_result", name), Span::call_site()))
+                .collect();
+            let factories: Vec<&ConcurrentFactory> = joined.iter().map(|&index| concurrent_factories[index].as_ref().unwrap()).collect();
+            let take_overrides = factories.iter().map(|factory| &factory.take_override);
+            // Joined without type erasure, so the build future stays `Send` if every factory is
+            let futures: Vec<TokenStream> = factories
+                .iter()
+                .zip(&slots)
+                .map(|(factory, slot)| {
+                    let result = &factory.result;
+                    quote! { async { #slot = Some(#result); } }
+                })
+                .collect();
+            let joined = futures
+                .into_iter()
+                .rev()
+                .reduce(|rest, future| quote! { service_builder::future::join(#future, #rest) })
+                .unwrap();
+            statements.push(quote! {
+                #(#take_overrides)*
+                #(let mut #slots = None;)*
+                #joined.await;
+                #(let #names = #slots.unwrap()?;)*
+            });
+        }
+    }
+
+    quote! { #(#statements)* }
+}
+
+/// The `T` in a `Weak<T>` or `Arc<T>` field type, with `wrapper` naming the outer type.
+fn wrapped_type(ty: &Type, wrapper: &str) -> Option<Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(target) => Some(target.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `ty` is `Self` or the struct's own name.
+fn is_self_type(ty: &Type, struct_name: &Ident) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.is_ident("Self") || path.path.is_ident(struct_name),
+        _ => false,
+    }
+}
+
+/// The type as written in the struct, e.g. `Arc<dyn Cache>`, for dependency listings.
+pub(crate) fn type_string(ty: &Type) -> String {
+    quote!(#ty)
+        .to_string()
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ::", "::")
+        .replace(":: ", "::")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
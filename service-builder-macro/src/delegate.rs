@@ -0,0 +1,170 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    braced, bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    FnArg, Ident, ItemTrait, Pat, PatIdent, Path, Token, TraitItem,
+};
+
+/// The name of the `macro_rules!` that `#[delegatable]` generates for a trait.
+pub fn delegate_macro_name(trait_name: &Ident) -> Ident {
+    format_ident!("__delegate_{}", trait_name)
+}
+
+/// Emits the trait unchanged, along with a `macro_rules!` that hands the trait's definition
+/// to `__delegate_impl!`, so that `#[builder(delegate(Trait))]` can see its methods.
+pub fn expand_delegatable(item: ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item.generics, "delegatable traits cannot be generic"));
+    }
+    let macro_name = delegate_macro_name(&item.ident);
+
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        macro_rules! #macro_name {
+            ($($args:tt)*) => {
+                service_builder::__delegate_impl! { { #item } $($args)* }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name;
+    })
+}
+
+/// `{ trait definition } { trait path } { impl header } field [method = path, ...]`
+pub struct DelegateImplInput {
+    item: ItemTrait,
+    trait_path: Path,
+    header: TokenStream,
+    field: Ident,
+    except: Punctuated<Except, Token![,]>,
+}
+
+/// A trait method that calls `target` instead of the field.
+struct Except {
+    method: Ident,
+    target: Path,
+}
+
+impl Parse for Except {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let method = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let target = input.parse()?;
+        Ok(Except { method, target })
+    }
+}
+
+impl Parse for DelegateImplInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+        let item = content.parse()?;
+        let content;
+        braced!(content in input);
+        let trait_path = content.parse()?;
+        let content;
+        braced!(content in input);
+        let header = content.parse()?;
+        let field = input.parse()?;
+        let content;
+        bracketed!(content in input);
+        let except = content.parse_terminated(Except::parse, Token![,])?;
+        Ok(DelegateImplInput { item, trait_path, header, field, except })
+    }
+}
+
+/// Generates `impl Trait for Struct`, forwarding every method to `self.field`.
+///
+/// Methods listed in `except` call the function given for them instead, with the same arguments.
+pub fn expand_delegate_impl(input: DelegateImplInput) -> syn::Result<TokenStream> {
+    let DelegateImplInput { item, trait_path, header, field, except } = input;
+
+    let mut methods = Vec::new();
+    for trait_item in &item.items {
+        let method = match trait_item {
+            TraitItem::Fn(method) => method,
+            TraitItem::Type(_) | TraitItem::Const(_) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!("cannot delegate `{}`: only traits with methods can be delegated", item.ident),
+                ))
+            }
+            _ => continue,
+        };
+
+        // Forwarded arguments get fresh names, as trait methods may use patterns or `_`
+        let mut sig = method.sig.clone();
+        let mut args = Vec::new();
+        let mut has_receiver = false;
+        for (index, input) in sig.inputs.iter_mut().enumerate() {
+            match input {
+                FnArg::Receiver(_) => has_receiver = true,
+                FnArg::Typed(typed) => {
+                    let arg = format_ident!("arg{}", index);
+                    *typed.pat = Pat::Ident(PatIdent {
+                        attrs: Vec::new(),
+                        by_ref: None,
+                        mutability: None,
+                        ident: arg.clone(),
+                        subpat: None,
+                    });
+                    args.push(arg);
+                }
+            }
+        }
+
+        let name = &sig.ident;
+        let target = except.iter().find(|excepted| excepted.method == *name).map(|excepted| &excepted.target);
+        let call = if let Some(target) = target {
+            if has_receiver {
+                quote! { #target(self, #(#args),*) }
+            } else {
+                quote! { #target(#(#args),*) }
+            }
+        } else if has_receiver {
+            quote! { self.#field.#name(#(#args),*) }
+        } else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("cannot delegate associated function `{0}` without `self`; give it in except({0} = \"...\")", name),
+            ));
+        };
+        let call = if sig.asyncness.is_some() { quote! { #call.await } } else { call };
+        let call = if sig.unsafety.is_some() { quote! { unsafe { #call } } } else { call };
+
+        // `Self::name` resolves to the trait method itself when the struct has no inherent method of
+        // that name, so a target that names the method being implemented is checked for recursion
+        let deny_recursion = target.map(|_| quote! { #[deny(unconditional_recursion)] });
+
+        methods.push(quote! {
+            #deny_recursion
+            #sig {
+                #[allow(unused_imports)]
+                use #trait_path as _;
+                #call
+            }
+        });
+    }
+
+    for excepted in &except {
+        let is_method = item.items.iter().any(|trait_item| matches!(trait_item, TraitItem::Fn(method) if method.sig.ident == excepted.method));
+        if !is_method {
+            return Err(syn::Error::new(
+                excepted.method.span(),
+                format!("`{}` is not a method of `{}`", excepted.method, item.ident),
+            ));
+        }
+    }
+
+    Ok(quote! {
+        #header {
+            #(#methods)*
+        }
+    })
+}
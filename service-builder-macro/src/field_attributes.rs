@@ -1,13 +1,39 @@
-use syn::{Attribute, Field, parse::Parse, Token, punctuated::Punctuated, parse::ParseStream};
+use syn::{Attribute, Field, parse::Parse, Token};
 
 #[derive(Debug, Default)]
 pub struct FieldAttributes {
     pub getter: bool,
     pub setter: bool,
+    pub setter_check: Option<String>,
     pub builder: bool,
     pub required: bool,
     pub optional: bool,
     pub default: Option<DefaultValue>,
+    pub factory: Option<String>,
+    pub async_factory: bool,
+    pub overridable: bool,
+    pub lifecycle: bool,
+    pub health: bool,
+    pub inject: bool,
+    pub inject_name: Option<String>,
+    pub inject_all: bool,
+    pub lazy: bool,
+    pub weak: bool,
+    pub graph: bool,
+    pub swappable: bool,
+    pub observe: bool,
+    /// Wrappers applied to the built value, in declaration order
+    pub decorators: Vec<String>,
+    pub delegates: Vec<Delegation>,
+}
+
+/// A trait implemented for the struct by forwarding to the field, from
+/// `#[builder(delegate(Trait, except(method = "Self::other", ...)))]`.
+#[derive(Debug, Clone)]
+pub struct Delegation {
+    pub trait_path: syn::Path,
+    /// Trait methods that call the given function instead of the field
+    pub except: Vec<(syn::Ident, syn::Path)>,
 }
 
 #[derive(Debug, Clone)]
@@ -23,15 +49,6 @@ struct FieldConfig {
     setter: bool,
 }
 
-#[derive(Debug)]
-struct FieldConfigs(Punctuated<FieldConfig, Token![,]>);
-
-impl Parse for FieldConfigs {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(FieldConfigs(Punctuated::parse_terminated(input)?))
-    }
-}
-
 impl Parse for FieldConfig {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let name = input.parse::<syn::Ident>()?.to_string();
@@ -42,16 +59,13 @@ impl Parse for FieldConfig {
 
         let nested_meta = content.parse_terminated(syn::Meta::parse, Token![,])?;
         for meta in nested_meta {
-            match meta {
-                syn::Meta::Path(path) => {
-                    let ident = path.get_ident().unwrap().to_string();
-                    if ident == "getter" {
-                        getter = true;
-                    } else if ident == "setter" {
-                        setter = true;
-                    }
-                },
-                _ => {}
+            if let syn::Meta::Path(path) = meta {
+                let ident = path.get_ident().unwrap().to_string();
+                if ident == "getter" {
+                    getter = true;
+                } else if ident == "setter" {
+                    setter = true;
+                }
             }
         }
 
@@ -64,7 +78,7 @@ impl Parse for FieldConfig {
 }
 
 impl FieldAttributes {
-    pub fn from_field(field: &Field, struct_attrs: &[Attribute], field_attrs: &[Attribute]) -> Self {
+    pub fn from_field(field: &Field, struct_attrs: &[Attribute], field_attrs: &[Attribute]) -> syn::Result<Self> {
         let mut attrs = FieldAttributes {
             builder: true, // Default to true for backward compatibility
             required: true, // Default to true for backward compatibility
@@ -75,11 +89,23 @@ impl FieldAttributes {
         // Process field-level attributes first
         for attr in field_attrs {
             if attr.path().is_ident("builder") {
-                let _ = attr.parse_nested_meta(|meta| {
+                attr.parse_nested_meta(|meta| {
                     if meta.path.is_ident("getter") {
                         attrs.getter = true;
                     } else if meta.path.is_ident("setter") {
                         attrs.setter = true;
+                        if meta.input.peek(syn::token::Paren) {
+                            // Parse #[builder(setter(check = "path::to::check"))]
+                            meta.parse_nested_meta(|setter| {
+                                if setter.path.is_ident("check") {
+                                    let check: syn::LitStr = setter.value()?.parse()?;
+                                    attrs.setter_check = Some(check.value());
+                                    Ok(())
+                                } else {
+                                    Err(setter.error("unsupported setter option"))
+                                }
+                            })?;
+                        }
                     } else if meta.path.is_ident("skip") {
                         attrs.builder = false;
                     } else if meta.path.is_ident("optional") {
@@ -89,17 +115,97 @@ impl FieldAttributes {
                         attrs.required = false;
                         if meta.input.peek(Token![=]) {
                             // Parse #[builder(default = "expression")]
-                            let _ = meta.input.parse::<Token![=]>();
-                            if let Ok(expr) = meta.input.parse::<syn::LitStr>() {
-                                attrs.default = Some(DefaultValue::Expression(expr.value()));
-                            }
+                            let expr: syn::LitStr = meta.value()?.parse()?;
+                            attrs.default = Some(DefaultValue::Expression(expr.value()));
                         } else {
                             // Parse #[builder(default)]
                             attrs.default = Some(DefaultValue::Default);
                         }
+                    } else if meta.path.is_ident("factory") {
+                        // Parse #[builder(factory = "expression")]
+                        attrs.required = false;
+                        let expr: syn::LitStr = meta.value()?.parse()?;
+                        attrs.factory = Some(expr.value());
+                    } else if meta.path.is_ident("async_factory") {
+                        // Parse #[builder(async_factory = "expression")]
+                        attrs.required = false;
+                        attrs.async_factory = true;
+                        let expr: syn::LitStr = meta.value()?.parse()?;
+                        attrs.factory = Some(expr.value());
+                    } else if meta.path.is_ident("overridable") {
+                        attrs.overridable = true;
+                    } else if meta.path.is_ident("lifecycle") {
+                        attrs.lifecycle = true;
+                    } else if meta.path.is_ident("health") {
+                        attrs.health = true;
+                    } else if meta.path.is_ident("weak") {
+                        // Weak back-references default to `Weak::new()` until wired
+                        attrs.weak = true;
+                        attrs.required = false;
+                        attrs.default = Some(DefaultValue::Default);
+                    } else if meta.path.is_ident("decorate") {
+                        // Parse #[builder(decorate = "with_logging")], which may be repeated
+                        let decorator: syn::LitStr = meta.value()?.parse()?;
+                        attrs.decorators.push(decorator.value());
+                    } else if meta.path.is_ident("delegate") {
+                        // Parse #[builder(delegate(Trait))] or
+                        // #[builder(delegate(Trait, except(method = "Self::other")))]
+                        let mut trait_path = None;
+                        let mut except = Vec::new();
+                        meta.parse_nested_meta(|delegate| {
+                            if delegate.path.is_ident("except") {
+                                delegate.parse_nested_meta(|method| {
+                                    let name = method.path.require_ident()?.clone();
+                                    if !method.input.peek(syn::Token![=]) {
+                                        return Err(method.error(format!(
+                                            "except({0}) must name the function to call instead, e.g. except({0} = \"Self::{0}\")",
+                                            name
+                                        )));
+                                    }
+                                    let target: syn::LitStr = method.value()?.parse()?;
+                                    except.push((name, target.parse()?));
+                                    Ok(())
+                                })
+                            } else if trait_path.is_none() {
+                                trait_path = Some(delegate.path);
+                                Ok(())
+                            } else {
+                                Err(delegate.error("expected a single trait, then except(...)"))
+                            }
+                        })?;
+                        let trait_path = trait_path.ok_or_else(|| meta.error("expected a trait to delegate"))?;
+                        attrs.delegates.push(Delegation { trait_path, except });
+                    } else if meta.path.is_ident("observe") {
+                        attrs.observe = true;
+                    } else if meta.path.is_ident("swappable") {
+                        attrs.swappable = true;
+                    } else if meta.path.is_ident("graph") {
+                        attrs.graph = true;
+                    } else if meta.path.is_ident("lazy") {
+                        attrs.lazy = true;
+                    } else if meta.path.is_ident("inject_all") {
+                        attrs.inject_all = true;
+                    } else if meta.path.is_ident("inject") {
+                        attrs.inject = true;
+                        if meta.input.peek(syn::token::Paren) {
+                            // Parse #[builder(inject(name = "primary"))] or its alias
+                            // #[builder(inject(qualifier = "primary"))]
+                            let mut name = None;
+                            meta.parse_nested_meta(|inject| {
+                                if inject.path.is_ident("name") || inject.path.is_ident("qualifier") {
+                                    name = Some(inject.value()?.parse::<syn::LitStr>()?.value());
+                                    Ok(())
+                                } else {
+                                    Err(inject.error("unsupported inject option"))
+                                }
+                            })?;
+                            attrs.inject_name = name;
+                        }
+                    } else {
+                        return Err(meta.error("unsupported builder option"));
                     }
                     Ok(())
-                });
+                })?;
             }
         }
 
@@ -121,6 +227,6 @@ impl FieldAttributes {
             }
         }
 
-        attrs
+        Ok(attrs)
     }
 }
\ No newline at end of file
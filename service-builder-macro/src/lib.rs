@@ -4,10 +4,16 @@
 //! which generates builder pattern code along with optional getters and setters.
 
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, ItemTrait};
 
 mod builder;
+mod delegate;
 mod field_attributes;
+mod ordering;
+mod services;
+mod struct_attributes;
+
+use struct_attributes::StructAttributes;
 
 /// Implements the builder pattern for a struct, with optional getter and setter methods.
 ///
@@ -19,6 +25,48 @@ mod field_attributes;
 /// - `#[builder(default)]`: Field uses `Default::default()` if not provided
 /// - `#[builder(default = "expression")]`: Field uses custom default expression
 /// - `#[builder(optional)]`: For `Option<T>` fields, defaults to `None`
+/// - `#[builder(factory = "expression")]`: Field is computed in `build()` from a `Result`-returning
+///   expression that may borrow other fields by name; errors become `BuildError::InitializationError`
+/// - `#[builder(factory = "expression", overridable)]`: Factory field that can also be set on the builder
+/// - `#[builder(async_factory = "expression")]`: Like `factory`, but the expression is a future that is
+///   awaited; requires `#[builder(async)]` on the struct
+/// - `#[builder(inject)]`: Field can be resolved by its type from a `ServiceRegistry` with the generated
+///   `fill_from`/`build_from` builder methods; `#[builder(inject(qualifier = "local"))]` uses a qualified
+///   service (`name = "..."` is an alias)
+/// - `#[builder(inject_all)]`: `Vec<Arc<T>>` field filled by `fill_from`/`build_from` with every service bound
+///   to `T` with `ServiceRegistry::register_many`
+/// - `#[builder(lazy)]`: Field is created on first use by a factory closure given to the builder; generates
+///   `get_field()` and `try_get_field()`, which returns `BuildError::InitializationError` if the factory fails
+/// - `#[builder(weak)]`: `Weak<T>` field defaulting to `Weak::new()`; `get_field()` upgrades it to `Option<Arc<T>>`.
+///   `Weak<Self>` fields are set by the generated `build_cyclic()`/`build_cyclic_with()` builder methods
+/// - `#[builder(decorate = "with_logging")]`: Passes the built value through a `Fn(T) -> T` wrapper; repeated
+///   decorators apply in declaration order, the first one innermost
+/// - `#[builder(delegate(Trait))]`: Implements `Trait` for the struct by forwarding to the field; methods in
+///   `delegate(Trait, except(method = "Self::other"))` call the given function instead. `Trait` must be `#[delegatable]`
+/// - `#[builder(swappable)]`: `Arc<T>` field that can be replaced at runtime; generates `load_field()` and
+///   `swap_field(new)`, which takes `&self` and returns the previous service
+/// - `#[builder(setter(check = "positive"))]`: `build()` and the setter validate the value with a
+///   `fn(&T) -> Result<(), E>`; the setter returns `Result<(), BuildError>`, leaving the field unchanged on failure
+/// - `#[builder(observe)]`: Generates `set_field()`, which notifies listeners registered with
+///   `subscribe_field(|old, new| ...)` after the update; `unsubscribe_field(subscription)` removes one
+/// - `#[builder(graph)]`: Expands the field's own dependencies in the struct's `graph()`
+///
+/// # Struct Attributes
+///
+/// - `#[builder(init = "Self::init")]`: Runs a `fn(&mut Self) -> Result<(), E>` hook after the
+///   struct is assembled; an `Err` is returned as `BuildError::InitializationError`
+/// - `#[builder(invariant = "Self::validate")]`: Checks a `fn(&Self) -> Result<(), E>` at the end of `build()`
+///   and in every setter, which then returns `Result<(), BuildError>` and rolls back on failure
+/// - `#[builder(update)]`: Generates `update(|builder| ...)`, which rebuilds the struct from a builder holding its
+///   current values and replaces it only if the build succeeds; builder fields must be `Clone`
+/// - `#[builder(async)]`: Generates async `build()`/`build_with_defaults()`; the `init` hook is awaited
+/// - `#[builder(async, concurrent)]`: Awaits independent async factories together
+/// - `#[builder(lifecycle)]`: Generates `start_all()`/`stop_all()` over fields marked `#[builder(lifecycle)]`
+/// - `#[builder(health)]`: Generates `health()` over fields marked `#[builder(health)]`
+/// - `#[builder(global)]`: Generates `init_global(builder)`, `global()` and `try_global()` for a process-wide
+///   instance, plus a `#[cfg(test)]` `override_global(service)` guard; not supported on generic structs
+/// - `#[builder(scope = "singleton" | "transient" | "scoped")]`: Implements `container::Scoped`,
+///   the scope a `Container` registers the struct with
 ///
 /// # Example
 ///
@@ -50,10 +98,115 @@ mod field_attributes;
 /// service.set_enabled(false);
 /// ```
 #[proc_macro_attribute]
-pub fn builder(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn builder(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut struct_attrs = StructAttributes::default();
+    let attr_parser = syn::meta::parser(|meta| struct_attrs.parse(meta));
+    parse_macro_input!(attr with attr_parser);
     let input = parse_macro_input!(item as DeriveInput);
-    match builder::expand_builder(input) {
+    match builder::expand_builder(input, struct_attrs) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Declares an application's services and how each is provided, and generates a struct
+/// holding all of them with a `build()` function that wires them in dependency order.
+///
+/// Each entry is `name: Type = provider`, where the provider is either:
+///
+/// - an expression, which can refer to other services by name and use `?`, or
+/// - `auto`, which builds the `#[builder]` struct `Type` with `Type::builder()`, passing a clone of
+///   the declared service of the same type to each builder field, then calls `build()?`.
+///   `auto(field: service, ...)` names the service for the listed fields instead.
+///
+/// The fields of an `auto` service are read from a hidden macro generated by `#[builder]`, so the
+/// struct must be defined in the same crate. Required fields without a service of their type,
+/// several services of a field's type, undeclared services and dependency cycles are reported as
+/// compile errors, the latter with the full cycle path.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use service_builder::services;
+///
+/// services! {
+///     pub struct AppServices {
+///         // `repository` and `cache` are set from `repo` and `cache`, found by type
+///         user_service: UserService = auto,
+///         repo: Arc<dyn UserRepository> = Arc::new(PgUserRepository::new()),
+///         cache: Arc<dyn Cache> = Arc::new(MemoryCache::builder().build()?),
+///     }
+/// }
+///
+/// let services = AppServices::build()?;
+/// ```
+#[proc_macro]
+pub fn services(input: TokenStream) -> TokenStream {
+    let raw = proc_macro2::TokenStream::from(input.clone());
+    let input = parse_macro_input!(input as services::ServicesInput);
+    match services::expand_services(services::ServicesImplInput::new(raw, input)) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+/// Makes a trait available to `#[builder(delegate(Trait))]` fields.
+///
+/// A struct's attribute macro cannot see the methods of other items, so this records the
+/// trait's definition in a hidden `macro_rules!` next to it, which the builder invokes to
+/// generate the forwarding impl. The trait must not be generic or have associated types or
+/// constants, and delegation works within the crate defining the trait. Methods listed in
+/// `except(method = "path")` call the function at `path` with the method's arguments instead.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use service_builder::{builder, delegatable};
+///
+/// #[delegatable]
+/// trait UserRepository {
+///     fn find(&self, id: u32) -> Option<User>;
+///     fn save(&self, user: User);
+/// }
+///
+/// #[builder]
+/// struct CachedRepository {
+///     // `find` calls `CachedRepository::cached_find`, `save` is forwarded to `inner`
+///     #[builder(delegate(UserRepository, except(find = "Self::cached_find")))]
+///     inner: Arc<dyn UserRepository>,
+///     cache: Cache,
+/// }
+///
+/// impl CachedRepository {
+///     fn cached_find(&self, id: u32) -> Option<User> {
+///         self.cache.get(id).or_else(|| self.inner.find(id))
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn delegatable(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemTrait);
+    match delegate::expand_delegatable(item) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __services_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as services::ServicesImplInput);
+    match services::expand_services(input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __delegate_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as delegate::DelegateImplInput);
+    match delegate::expand_delegate_impl(input) {
         Ok(expanded) => expanded.into(),
         Err(err) => err.to_compile_error().into(),
     }
-}
\ No newline at end of file
+}
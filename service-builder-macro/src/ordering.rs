@@ -0,0 +1,90 @@
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use syn::Ident;
+
+/// Collects the names in `field_names` that `tokens` refers to as plain identifiers.
+///
+/// Identifiers reached through `.` or `::` (method calls, field accesses, paths)
+/// are not treated as references to other fields.
+pub fn referenced_fields(tokens: &TokenStream, field_names: &[&Ident]) -> Vec<Ident> {
+    let mut found = Vec::new();
+    collect_references(tokens.clone(), field_names, &mut found);
+    found
+}
+
+fn collect_references(tokens: TokenStream, field_names: &[&Ident], found: &mut Vec<Ident>) {
+    let mut after_accessor = false;
+    let mut after_joint_colon = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                if !after_accessor
+                    && field_names.iter().any(|name| **name == ident)
+                    && !found.contains(&ident)
+                {
+                    found.push(ident);
+                }
+                after_accessor = false;
+                after_joint_colon = false;
+            }
+            TokenTree::Punct(punct) => {
+                // `a.b` and `a::b` both end with the punct right before `b`
+                after_accessor = punct.as_char() == '.' || (punct.as_char() == ':' && after_joint_colon);
+                after_joint_colon = punct.as_char() == ':' && punct.spacing() == Spacing::Joint;
+            }
+            TokenTree::Group(group) => {
+                collect_references(group.stream(), field_names, found);
+                after_accessor = false;
+                after_joint_colon = false;
+            }
+            TokenTree::Literal(_) => {
+                after_accessor = false;
+                after_joint_colon = false;
+            }
+        }
+    }
+}
+
+/// Orders fields so that every field comes after the fields it depends on.
+///
+/// Fields keep their declaration order wherever the dependencies allow it. If the
+/// dependencies form a cycle, the field indices along the cycle are returned, with
+/// the first field repeated at the end.
+pub fn resolution_order(dependencies: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut order = Vec::with_capacity(dependencies.len());
+    let mut resolved = vec![false; dependencies.len()];
+
+    while order.len() < dependencies.len() {
+        let next = (0..dependencies.len()).find(|&index| {
+            !resolved[index] && dependencies[index].iter().all(|&dep| resolved[dep])
+        });
+
+        match next {
+            Some(index) => {
+                resolved[index] = true;
+                order.push(index);
+            }
+            None => {
+                let start = (0..dependencies.len()).find(|&index| !resolved[index]).unwrap();
+                return Err(find_cycle(start, dependencies, &resolved));
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+/// Follows unresolved dependencies from `start` until a field repeats.
+fn find_cycle(start: usize, dependencies: &[Vec<usize>], resolved: &[bool]) -> Vec<usize> {
+    let mut path = vec![start];
+    let mut current = start;
+    loop {
+        // Every unresolved field has at least one unresolved dependency
+        current = *dependencies[current].iter().find(|&&dep| !resolved[dep]).unwrap();
+        if let Some(position) = path.iter().position(|&index| index == current) {
+            let mut cycle = path.split_off(position);
+            cycle.push(current);
+            return cycle;
+        }
+        path.push(current);
+    }
+}
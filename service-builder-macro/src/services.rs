@@ -0,0 +1,329 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Expr, Ident, Path, PathArguments, Token, Type, Visibility,
+};
+
+use crate::builder::type_string;
+use crate::ordering::{referenced_fields, resolution_order};
+
+/// The name of the `macro_rules!` that `#[builder]` generates to export a struct's builder fields.
+pub fn fields_macro_name(struct_name: &Ident) -> Ident {
+    format_ident!("__builder_fields_{}", struct_name)
+}
+
+/// `services! { pub struct AppServices { name: Type = provider, ... } }`
+pub struct ServicesInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    services: Punctuated<ServiceDecl, Token![,]>,
+}
+
+struct ServiceDecl {
+    name: Ident,
+    ty: Type,
+    provider: Provider,
+}
+
+enum Provider {
+    /// `auto` or `auto(field: service, service)`, built with `Type::builder()`
+    Auto(Vec<AutoArg>),
+    /// Any expression evaluating to the service; may use `?` and other services by name
+    Expr(Expr),
+}
+
+/// A builder field set from a service, `field: service` or just `service` if they share a name.
+struct AutoArg {
+    field: Ident,
+    service: Ident,
+}
+
+/// `{ services! input } { builder fields } ...`, passed along the `#[builder]` field macros of
+/// the `auto` services until every one of them has added its fields.
+pub struct ServicesImplInput {
+    raw: TokenStream,
+    input: ServicesInput,
+    field_blocks: Vec<TokenStream>,
+}
+
+/// A builder field exported by `#[builder]`: `name: Type => required | optional | lazy`.
+struct BuilderField {
+    name: Ident,
+    ty: Type,
+    kind: Ident,
+}
+
+impl Parse for ServicesInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse()?;
+        let content;
+        braced!(content in input);
+        let services = content.parse_terminated(ServiceDecl::parse, Token![,])?;
+        Ok(ServicesInput { attrs, vis, ident, services })
+    }
+}
+
+impl Parse for ServiceDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let provider = if is_auto(input) {
+            input.parse::<Ident>()?;
+            let mut args = Vec::new();
+            if input.peek(syn::token::Paren) {
+                let content;
+                parenthesized!(content in input);
+                for arg in content.parse_terminated(AutoArg::parse, Token![,])? {
+                    args.push(arg);
+                }
+            }
+            Provider::Auto(args)
+        } else {
+            Provider::Expr(input.parse()?)
+        };
+        Ok(ServiceDecl { name, ty, provider })
+    }
+}
+
+impl Parse for AutoArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let field: Ident = input.parse()?;
+        if input.parse::<Option<Token![:]>>()?.is_some() {
+            let service = input.parse()?;
+            Ok(AutoArg { field, service })
+        } else {
+            Ok(AutoArg { field: field.clone(), service: field })
+        }
+    }
+}
+
+impl Parse for ServicesImplInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+        let raw = content.fork().parse()?;
+        let services = content.parse()?;
+        let mut field_blocks = Vec::new();
+        while !input.is_empty() {
+            let content;
+            braced!(content in input);
+            field_blocks.push(content.parse()?);
+        }
+        Ok(ServicesImplInput { raw, input: services, field_blocks })
+    }
+}
+
+impl Parse for BuilderField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let kind = input.parse()?;
+        Ok(BuilderField { name, ty, kind })
+    }
+}
+
+impl ServicesImplInput {
+    pub fn new(raw: TokenStream, input: ServicesInput) -> Self {
+        ServicesImplInput { raw, input, field_blocks: Vec::new() }
+    }
+}
+
+/// The path of the field macro generated next to an `auto` service's struct.
+fn fields_macro_path(ty: &Type) -> syn::Result<Path> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut path = type_path.path.clone();
+            let last = path.segments.last_mut().unwrap();
+            last.ident = fields_macro_name(&last.ident);
+            last.arguments = PathArguments::None;
+            Ok(path)
+        }
+        _ => Err(syn::Error::new_spanned(ty, "auto services must be #[builder] structs")),
+    }
+}
+
+/// `auto` on its own or followed by its argument list, as opposed to an expression starting with `auto`
+fn is_auto(input: ParseStream) -> bool {
+    let fork = input.fork();
+    match fork.parse::<Ident>() {
+        Ok(ident) if ident == "auto" => fork.is_empty() || fork.peek(Token![,]) || fork.peek(syn::token::Paren),
+        _ => false,
+    }
+}
+
+pub fn expand_services(input: ServicesImplInput) -> syn::Result<TokenStream> {
+    let ServicesImplInput { raw, input, field_blocks } = input;
+    let ServicesInput { attrs, vis, ident, services } = input;
+    let services: Vec<ServiceDecl> = services.into_iter().collect();
+    let names: Vec<&Ident> = services.iter().map(|service| &service.name).collect();
+
+    // Collect the builder fields of every `auto` service first, one callback at a time
+    let autos: Vec<usize> = (0..services.len())
+        .filter(|&index| matches!(services[index].provider, Provider::Auto(_)))
+        .collect();
+    if let Some(&next) = autos.get(field_blocks.len()) {
+        let fields_macro = fields_macro_path(&services[next].ty)?;
+        return Ok(quote! {
+            #fields_macro! { { #raw } #({ #field_blocks })* }
+        });
+    }
+    let mut auto_fields = Vec::new();
+    for block in field_blocks {
+        let fields = syn::parse::Parser::parse2(Punctuated::<BuilderField, Token![,]>::parse_terminated, block)?;
+        auto_fields.push(fields.into_iter().collect::<Vec<_>>());
+    }
+
+    // Every service a provider refers to must be declared, and `auto` services need a provider
+    // for each required builder field
+    let mut dependencies = Vec::new();
+    let mut auto_setters = Vec::new();
+    for (index, service) in services.iter().enumerate() {
+        let referenced = match &service.provider {
+            Provider::Auto(args) => {
+                let fields = &auto_fields[autos.iter().position(|&auto| auto == index).unwrap()];
+                let setters = auto_arguments(service, index, args, fields, &services)?;
+                let referenced = setters.iter().map(|(_, service)| service.clone()).collect();
+                auto_setters.push(setters);
+                referenced
+            }
+            Provider::Expr(expr) => referenced_fields(&expr.to_token_stream(), &names),
+        };
+        dependencies.push(
+            referenced
+                .iter()
+                .map(|name| names.iter().position(|candidate| *candidate == name).unwrap())
+                .filter(|&dependency| dependency != index)
+                .collect::<Vec<usize>>(),
+        );
+    }
+
+    let order = resolution_order(&dependencies).map_err(|cycle| {
+        let path: Vec<String> = cycle.iter().map(|&index| names[index].to_string()).collect();
+        syn::Error::new(
+            names[cycle[0]].span(),
+            format!("service dependency cycle: {}", path.join(" -> ")),
+        )
+    })?;
+
+    let fields = services.iter().map(|service| {
+        let name = &service.name;
+        let ty = &service.ty;
+        quote! { #vis #name: #ty }
+    });
+
+    let statements = order.iter().map(|&index| {
+        let service = &services[index];
+        let name = &service.name;
+        let ty = &service.ty;
+        let value = match &service.provider {
+            Provider::Auto(_) => {
+                let setters = auto_setters[autos.iter().position(|&auto| auto == index).unwrap()]
+                    .iter()
+                    .map(|(field, dependency)| quote! { .#field(#dependency.clone()) });
+                quote! { <#ty>::builder() #(#setters)* .build()? }
+            }
+            Provider::Expr(expr) => quote! { #expr },
+        };
+        quote! { let #name: #ty = #value; }
+    });
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #ident {
+            #(#fields),*
+        }
+
+        impl #ident {
+            /// Builds every service, each after the services it depends on.
+            #[track_caller]
+            pub fn build() -> Result<Self, service_builder::error::BuildError> {
+                #(#statements)*
+                Ok(Self {
+                    #(#names),*
+                })
+            }
+        }
+    })
+}
+
+/// Pairs every builder field of an `auto` service with the service it is set from: the one given
+/// in `auto(field: service)`, or otherwise the only service declared with the field's type.
+fn auto_arguments(
+    service: &ServiceDecl,
+    index: usize,
+    args: &[AutoArg],
+    fields: &[BuilderField],
+    services: &[ServiceDecl],
+) -> syn::Result<Vec<(Ident, Ident)>> {
+    let mut setters = Vec::new();
+    for arg in args {
+        if !services.iter().any(|candidate| candidate.name == arg.service) {
+            return Err(syn::Error::new(
+                arg.service.span(),
+                format!("no provider for service `{}` required by `{}`", arg.service, service.name),
+            ));
+        }
+        if !fields.iter().any(|field| field.name == arg.field) {
+            return Err(syn::Error::new(
+                arg.field.span(),
+                format!("`{}` has no builder field `{}`", type_string(&service.ty), arg.field),
+            ));
+        }
+        setters.push((arg.field.clone(), arg.service.clone()));
+    }
+
+    for field in fields {
+        if args.iter().any(|arg| arg.field == field.name) {
+            continue;
+        }
+        // Lazy builder fields take a factory closure rather than a service
+        if field.kind == "lazy" {
+            return Err(syn::Error::new(
+                service.name.span(),
+                format!(
+                    "`{}.{}` is lazy and cannot be provided by services!; build `{}` with an expression",
+                    service.name, field.name, type_string(&service.ty)
+                ),
+            ));
+        }
+        let field_type = type_string(&field.ty);
+        let providers: Vec<&Ident> = services
+            .iter()
+            .enumerate()
+            .filter(|&(candidate, provider)| candidate != index && type_string(&provider.ty) == field_type)
+            .map(|(_, provider)| &provider.name)
+            .collect();
+        match providers.as_slice() {
+            [provider] => setters.push((field.name.clone(), (*provider).clone())),
+            [] if field.kind == "optional" => {}
+            [] => {
+                return Err(syn::Error::new(
+                    service.name.span(),
+                    format!("no provider for `{}` required by `{}.{}`", field_type, service.name, field.name),
+                ))
+            }
+            providers => {
+                let names: Vec<String> = providers.iter().map(|name| format!("`{}`", name)).collect();
+                return Err(syn::Error::new(
+                    service.name.span(),
+                    format!(
+                        "services {} all provide `{}` for `{}.{}`; choose one with auto({}: service)",
+                        names.join(", "), field_type, service.name, field.name, field.name
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(setters)
+}
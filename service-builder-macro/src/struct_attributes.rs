@@ -0,0 +1,69 @@
+use proc_macro2::Ident;
+use syn::{meta::ParseNestedMeta, LitStr};
+
+/// Options given to the `#[builder(...)]` attribute on the struct itself.
+#[derive(Debug, Default)]
+pub struct StructAttributes {
+    pub init: Option<syn::Expr>,
+    pub invariant: Option<syn::Expr>,
+    pub is_async: bool,
+    pub concurrent: bool,
+    pub lifecycle: bool,
+    pub health: bool,
+    /// The `service_builder::container::Scope` variant named by `scope = "..."`
+    pub scope: Option<Ident>,
+    pub global: bool,
+    pub update: bool,
+}
+
+impl StructAttributes {
+    pub fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("init") {
+            // Parse #[builder(init = "path::to::hook")]
+            let hook: LitStr = meta.value()?.parse()?;
+            self.init = Some(hook.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("invariant") {
+            // Parse #[builder(invariant = "path::to::check")]
+            let invariant: LitStr = meta.value()?.parse()?;
+            self.invariant = Some(invariant.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("async") {
+            self.is_async = true;
+            Ok(())
+        } else if meta.path.is_ident("concurrent") {
+            self.concurrent = true;
+            Ok(())
+        } else if meta.path.is_ident("lifecycle") {
+            self.lifecycle = true;
+            Ok(())
+        } else if meta.path.is_ident("health") {
+            self.health = true;
+            Ok(())
+        } else if meta.path.is_ident("update") {
+            self.update = true;
+            Ok(())
+        } else if meta.path.is_ident("global") {
+            self.global = true;
+            Ok(())
+        } else if meta.path.is_ident("scope") {
+            // Parse #[builder(scope = "singleton" | "transient" | "scoped")]
+            let scope: LitStr = meta.value()?.parse()?;
+            let variant = match scope.value().as_str() {
+                "singleton" => "Singleton",
+                "transient" => "Transient",
+                "scoped" => "Scoped",
+                _ => {
+                    return Err(syn::Error::new(
+                        scope.span(),
+                        "expected scope to be \"singleton\", \"transient\" or \"scoped\"",
+                    ))
+                }
+            };
+            self.scope = Some(Ident::new(variant, scope.span()));
+            Ok(())
+        } else {
+            Err(meta.error("unsupported builder option"))
+        }
+    }
+}
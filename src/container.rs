@@ -0,0 +1,263 @@
+//! A service container that controls how long built services live.
+//!
+//! Services are registered with a provider, usually a call to a generated builder, and a
+//! [`Scope`]:
+//!
+//! - [`Scope::Singleton`]: built once and shared by the whole container
+//! - [`Scope::Transient`]: built again every time it is resolved
+//! - [`Scope::Scoped`]: built once per [`ServiceScope`], e.g. one `UnitOfWork` per request
+//!
+//! A provider that depends on itself, directly or through other providers, fails with
+//! `BuildError::CircularDependency` naming the whole cycle instead of recursing forever.
+//!
+//! ```ignore
+//! let mut container = Container::new();
+//! container.register_service(|_| Database::builder().url(url.clone()).build());
+//! container.register_service(|r| UnitOfWork::builder().database(r.resolve()?).build());
+//!
+//! let scope = container.create_scope();
+//! let unit_of_work = scope.resolve::<UnitOfWork>()?;
+//! ```
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::error::BuildError;
+
+/// How long a resolved service instance is reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Singleton,
+    Transient,
+    Scoped,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Singleton => write!(f, "singleton"),
+            Scope::Transient => write!(f, "transient"),
+            Scope::Scoped => write!(f, "scoped"),
+        }
+    }
+}
+
+/// The scope a service type is registered with, generated by `#[builder(scope = "...")]`.
+pub trait Scoped {
+    const SCOPE: Scope;
+}
+
+type Instance = Arc<dyn Any + Send + Sync>;
+type Provider = Box<dyn Fn(&Resolver<'_>) -> Result<Instance, BuildError> + Send + Sync>;
+
+struct Registration {
+    scope: Scope,
+    type_name: &'static str,
+    provider: Provider,
+}
+
+/// The root container holding registrations and singleton instances.
+#[derive(Default)]
+pub struct Container {
+    registrations: HashMap<TypeId, Registration>,
+    singletons: Mutex<HashMap<TypeId, Instance>>,
+}
+
+impl Container {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a provider for `T` with the given scope, replacing any previous registration.
+    pub fn register<T, F>(&mut self, scope: Scope, provider: F) -> &mut Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+        F: Fn(&Resolver<'_>) -> Result<Arc<T>, BuildError> + Send + Sync + 'static,
+    {
+        self.registrations.insert(
+            TypeId::of::<T>(),
+            Registration {
+                scope,
+                type_name: type_name::<T>(),
+                provider: Box::new(move |resolver| provider(resolver).map(|service| Arc::new(service) as Instance)),
+            },
+        );
+        self
+    }
+
+    /// Registers a provider for a `#[builder(scope = "...")]` service, using its declared scope.
+    pub fn register_service<T, F>(&mut self, provider: F) -> &mut Self
+    where
+        T: Scoped + Send + Sync + 'static,
+        F: Fn(&Resolver<'_>) -> Result<T, BuildError> + Send + Sync + 'static,
+    {
+        self.register::<T, _>(T::SCOPE, move |resolver| provider(resolver).map(Arc::new))
+    }
+
+    /// Resolves a singleton or transient `T`. Scoped services need a [`ServiceScope`].
+    pub fn resolve<T>(&self) -> Result<Arc<T>, BuildError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        Resolver::root(self).resolve()
+    }
+
+    /// Creates a child scope, which caches scoped instances until it is dropped.
+    pub fn create_scope(&self) -> ServiceScope<'_> {
+        ServiceScope {
+            container: self,
+            instances: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl fmt::Debug for Container {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut registrations: Vec<String> = self
+            .registrations
+            .values()
+            .map(|registration| format!("{} ({})", registration.type_name, registration.scope))
+            .collect();
+        registrations.sort();
+        f.debug_struct("Container")
+            .field("registrations", &registrations)
+            .finish()
+    }
+}
+
+/// A child of a [`Container`] that shares its singletons and owns its scoped instances.
+pub struct ServiceScope<'c> {
+    container: &'c Container,
+    instances: Mutex<HashMap<TypeId, Instance>>,
+}
+
+impl ServiceScope<'_> {
+    /// Resolves `T`, reusing this scope's instance for scoped services.
+    pub fn resolve<T>(&self) -> Result<Arc<T>, BuildError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        Resolver {
+            container: self.container,
+            scope: Some(self),
+            singleton: None,
+            stack: Vec::new(),
+        }
+        .resolve()
+    }
+}
+
+/// Passed to providers to resolve the services they depend on.
+pub struct Resolver<'a> {
+    container: &'a Container,
+    scope: Option<&'a ServiceScope<'a>>,
+    // The singleton being built, if any; it must not capture scoped services
+    singleton: Option<&'static str>,
+    // The services being built, outermost first, to report cycles
+    stack: Vec<(TypeId, &'static str)>,
+}
+
+impl<'a> Resolver<'a> {
+    fn root(container: &'a Container) -> Self {
+        Resolver {
+            container,
+            scope: None,
+            singleton: None,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Resolves a dependency of the service being built. An installed unnamed test override of
+    /// `T` takes precedence over its provider.
+    pub fn resolve<T>(&self) -> Result<Arc<T>, BuildError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        #[cfg(feature = "test-overrides")]
+        if let Some(service) = crate::overrides::lookup::<T>(None) {
+            return Ok(service);
+        }
+        let id = TypeId::of::<T>();
+        let registration = self
+            .container
+            .registrations
+            .get(&id)
+            .ok_or_else(|| BuildError::MissingDependency(type_name::<T>().to_string()))?;
+
+        if let Some(start) = self.stack.iter().position(|(in_flight, _)| *in_flight == id) {
+            let mut cycle: Vec<&str> = self.stack[start..].iter().map(|(_, name)| *name).collect();
+            cycle.push(registration.type_name);
+            return Err(BuildError::CircularDependency(cycle.join(" -> ")));
+        }
+        let mut stack = self.stack.clone();
+        stack.push((id, registration.type_name));
+
+        let instance = match registration.scope {
+            Scope::Singleton => {
+                let existing = self.container.singletons.lock().unwrap().get(&id).cloned();
+                match existing {
+                    Some(instance) => instance,
+                    None => {
+                        // Singletons never see the current scope, so they cannot capture scoped services
+                        let resolver = Resolver {
+                            container: self.container,
+                            scope: None,
+                            singleton: Some(registration.type_name),
+                            stack,
+                        };
+                        let instance = (registration.provider)(&resolver)?;
+                        self.container
+                            .singletons
+                            .lock()
+                            .unwrap()
+                            .entry(id)
+                            .or_insert(instance)
+                            .clone()
+                    }
+                }
+            }
+            Scope::Transient => (registration.provider)(&self.nested(stack))?,
+            Scope::Scoped => {
+                let scope = match (self.scope, self.singleton) {
+                    (Some(scope), _) => scope,
+                    (None, Some(singleton)) => {
+                        return Err(BuildError::ScopeViolation(format!(
+                            "singleton {} depends on scoped {}",
+                            singleton, registration.type_name
+                        )))
+                    }
+                    (None, None) => {
+                        return Err(BuildError::ScopeViolation(format!(
+                            "scoped {} resolved outside of a scope",
+                            registration.type_name
+                        )))
+                    }
+                };
+                let existing = scope.instances.lock().unwrap().get(&id).cloned();
+                match existing {
+                    Some(instance) => instance,
+                    None => {
+                        let instance = (registration.provider)(&self.nested(stack))?;
+                        scope.instances.lock().unwrap().entry(id).or_insert(instance).clone()
+                    }
+                }
+            }
+        };
+
+        Ok(instance
+            .downcast_ref::<Arc<T>>()
+            .expect("container instances are stored under their own type")
+            .clone())
+    }
+
+    fn nested(&self, stack: Vec<(TypeId, &'static str)>) -> Resolver<'a> {
+        Resolver {
+            container: self.container,
+            scope: self.scope,
+            singleton: self.singleton,
+            stack,
+        }
+    }
+}
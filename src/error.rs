@@ -1,4 +1,7 @@
 // service-builder/src/error.rs
+use std::fmt;
+use std::panic::Location;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,4 +17,118 @@ pub enum BuildError {
 
     #[error("Service build failed: {0}")]
     BuildFailed(String),
+
+    /// A service depends on itself through its providers, e.g. `AuthService -> UserService -> AuthService`.
+    #[error("Circular dependency: {0}")]
+    CircularDependency(String),
+
+    #[error("Service scope violation: {0}")]
+    ScopeViolation(String),
+
+    #[error("Service lifecycle failed: {0}")]
+    LifecycleFailed(LifecycleError),
+
+    /// Wraps an error returned by a generated `build()` with the places the
+    /// builder was created and built.
+    ///
+    /// `Display` shows the wrapped error followed by the locations. The wrapped error is not
+    /// reported as the [`source`](std::error::Error::source), so error reports that walk the
+    /// source chain print it once.
+    #[error("{inner} (builder created at {created_at}, built at {built_at})")]
+    Located {
+        inner: Box<BuildError>,
+        created_at: &'static Location<'static>,
+        built_at: &'static Location<'static>,
+    },
+}
+
+impl BuildError {
+    /// Records the builder creation site and the `build()` call site on this error.
+    pub fn located(
+        self,
+        created_at: &'static Location<'static>,
+        built_at: &'static Location<'static>,
+    ) -> Self {
+        BuildError::Located {
+            inner: Box::new(self),
+            created_at,
+            built_at,
+        }
+    }
+
+    /// Returns where the failing builder was created, if recorded.
+    pub fn created_at(&self) -> Option<&'static Location<'static>> {
+        match self {
+            BuildError::Located { created_at, .. } => Some(created_at),
+            _ => None,
+        }
+    }
+
+    /// Returns where `build()` was called on the failing builder, if recorded.
+    pub fn built_at(&self) -> Option<&'static Location<'static>> {
+        match self {
+            BuildError::Located { built_at, .. } => Some(built_at),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error without any location information.
+    pub fn kind(&self) -> &BuildError {
+        match self {
+            BuildError::Located { inner, .. } => inner.kind(),
+            other => other,
+        }
+    }
+
+    /// Consumes the error and returns the underlying error without any location information.
+    pub fn into_inner(self) -> BuildError {
+        match self {
+            BuildError::Located { inner, .. } => inner.into_inner(),
+            other => other,
+        }
+    }
 }
+
+/// The lifecycle phase during which components failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecyclePhase {
+    Start,
+    Stop,
+}
+
+impl fmt::Display for LifecyclePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecyclePhase::Start => write!(f, "start"),
+            LifecyclePhase::Stop => write!(f, "stop"),
+        }
+    }
+}
+
+/// A single component that failed to start or stop.
+#[derive(Debug)]
+pub struct ComponentFailure {
+    pub component: String,
+    pub phase: LifecyclePhase,
+    pub error: Box<dyn std::error::Error + Send + Sync>,
+}
+
+/// Every failure collected while starting or stopping a set of components.
+#[derive(Debug)]
+pub struct LifecycleError {
+    pub phase: LifecyclePhase,
+    pub failures: Vec<ComponentFailure>,
+}
+
+impl fmt::Display for LifecycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed", self.phase)?;
+        for (index, failure) in self.failures.iter().enumerate() {
+            let separator = if index == 0 { ": " } else { "; " };
+            write!(f, "{}{} ({}): {}", separator, failure.component, failure.phase, failure.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LifecycleError {}
@@ -0,0 +1,55 @@
+//! Minimal, runtime-agnostic future utilities used by async builders.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Polls `first` and `second` concurrently and completes once both have completed.
+///
+/// Results are expected to be written to the caller's own slots, which lets futures with
+/// different output types be awaited together. The futures are not type-erased, so the joined
+/// future is `Send` when both futures are. Generated builders nest it to await any number of
+/// factories together.
+pub fn join<A, B>(first: A, second: B) -> Join<A, B>
+where
+    A: Future<Output = ()>,
+    B: Future<Output = ()>,
+{
+    Join {
+        first: Some(Box::pin(first)),
+        second: Some(Box::pin(second)),
+    }
+}
+
+/// Future returned by [`join`].
+pub struct Join<A, B> {
+    first: Option<Pin<Box<A>>>,
+    second: Option<Pin<Box<B>>>,
+}
+
+impl<A, B> Future for Join<A, B>
+where
+    A: Future<Output = ()>,
+    B: Future<Output = ()>,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(first) = &mut self.first {
+            if first.as_mut().poll(cx).is_ready() {
+                self.first = None;
+            }
+        }
+        if let Some(second) = &mut self.second {
+            if second.as_mut().poll(cx).is_ready() {
+                self.second = None;
+            }
+        }
+
+        if self.first.is_none() && self.second.is_none() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
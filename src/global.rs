@@ -0,0 +1,75 @@
+//! Process-wide service instances, backing `#[builder(global)]` structs.
+
+use std::sync::RwLock;
+
+use crate::error::BuildError;
+
+/// Holds the global instance of a service.
+///
+/// Instances are leaked when installed, so the `&'static` references handed out by
+/// [`GlobalSlot::get`] stay valid even after a test override replaces them.
+pub struct GlobalSlot<T: 'static> {
+    instance: RwLock<Option<&'static T>>,
+}
+
+impl<T: 'static> GlobalSlot<T> {
+    pub const fn new() -> Self {
+        GlobalSlot {
+            instance: RwLock::new(None),
+        }
+    }
+
+    /// Installs `service`, or returns `BuildError::ConfigurationError` if an instance is already
+    /// installed. `name` is the service's name for the error message.
+    pub fn init(&self, service: T, name: &str) -> Result<(), BuildError> {
+        let mut instance = self.instance.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if instance.is_some() {
+            return Err(already_initialized(name));
+        }
+        *instance = Some(Box::leak(Box::new(service)));
+        Ok(())
+    }
+
+    /// Returns `BuildError::ConfigurationError` if an instance is already installed.
+    pub fn ensure_uninitialized(&self, name: &str) -> Result<(), BuildError> {
+        match self.get() {
+            Some(_) => Err(already_initialized(name)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self) -> Option<&'static T> {
+        *self.instance.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Installs `service` until the returned guard is dropped, whether or not an instance was
+    /// installed before.
+    pub fn replace(&'static self, service: T) -> GlobalOverride<T> {
+        let mut instance = self.instance.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let previous = instance.replace(Box::leak(Box::new(service)));
+        GlobalOverride { slot: self, previous }
+    }
+}
+
+impl<T: 'static> Default for GlobalSlot<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn already_initialized(name: &str) -> BuildError {
+    BuildError::ConfigurationError(format!("global {} is already initialized", name))
+}
+
+/// Restores the previous global instance, or none, when dropped.
+#[must_use = "the previous global instance is restored when the guard is dropped"]
+pub struct GlobalOverride<T: 'static> {
+    slot: &'static GlobalSlot<T>,
+    previous: Option<&'static T>,
+}
+
+impl<T: 'static> Drop for GlobalOverride<T> {
+    fn drop(&mut self) {
+        *self.slot.instance.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = self.previous;
+    }
+}
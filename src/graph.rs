@@ -0,0 +1,181 @@
+//! Dependency information generated for every `#[builder]` struct, and graphs of how services
+//! are wired that render to DOT (Graphviz) and JSON.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::health::write_json_string;
+
+/// How a field gets its value when the service is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    /// Must be set on the builder
+    Required,
+    /// Falls back to a default value when not set
+    Defaulted,
+    /// An `Option` that is `None` when not set
+    Optional,
+    /// Resolved from a `ServiceRegistry` by `#[builder(inject)]` or `#[builder(inject_all)]`
+    Injected,
+    /// Computed by a `factory` or `async_factory` expression
+    Factory,
+    /// Created on first use
+    Lazy,
+    /// A `Weak` back-reference
+    Weak,
+    /// Not set by the builder
+    Skipped,
+}
+
+impl DependencyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Required => "required",
+            DependencyKind::Defaulted => "defaulted",
+            DependencyKind::Optional => "optional",
+            DependencyKind::Injected => "injected",
+            DependencyKind::Factory => "factory",
+            DependencyKind::Lazy => "lazy",
+            DependencyKind::Weak => "weak",
+            DependencyKind::Skipped => "skipped",
+        }
+    }
+}
+
+/// A field of a service, as listed by the generated `dependency_info()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dependency {
+    pub field: &'static str,
+    /// The field's type as written in the struct
+    pub type_name: &'static str,
+    pub kind: DependencyKind,
+}
+
+/// A service and its dependencies, expanded for fields marked `#[builder(graph)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyGraph {
+    pub service: &'static str,
+    pub dependencies: Vec<GraphNode>,
+}
+
+/// A dependency in a [`DependencyGraph`], with the dependency's own graph if it was expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    pub dependency: Dependency,
+    pub graph: Option<DependencyGraph>,
+}
+
+impl DependencyGraph {
+    /// Creates the graph for `service`, expanding the dependencies that have a graph in `expanded`.
+    pub fn new(
+        service: &'static str,
+        dependencies: &[Dependency],
+        mut expanded: Vec<(&'static str, DependencyGraph)>,
+    ) -> Self {
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| GraphNode {
+                dependency: *dependency,
+                graph: expanded
+                    .iter()
+                    .position(|(field, _)| *field == dependency.field)
+                    .map(|index| expanded.remove(index).1),
+            })
+            .collect();
+        DependencyGraph { service, dependencies }
+    }
+
+    /// Renders the graph in the Graphviz DOT language, with one edge per field labelled with
+    /// the field name and its kind. Expanded services shared by several parents appear once;
+    /// other fields get a node of their own, such as `UserService.key_prefix`, labelled with
+    /// the field's type.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependencies {\n");
+        let _ = writeln!(dot, "    {};", dot_id(self.service));
+        let mut seen = BTreeSet::new();
+        self.visit_edges(&mut |edge| {
+            if seen.insert(edge.clone()) {
+                let _ = writeln!(dot, "    {}", edge);
+            }
+        });
+        dot.push('}');
+        dot
+    }
+
+    /// Renders the graph as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn edge(&self, node: &GraphNode, target: &str) -> String {
+        format!(
+            "{} -> {} [label={}];",
+            dot_id(self.service),
+            dot_id(target),
+            dot_id(&format!("{} ({})", node.dependency.field, node.dependency.kind.as_str()))
+        )
+    }
+
+    fn visit_edges(&self, visit: &mut dyn FnMut(String)) {
+        for node in &self.dependencies {
+            match &node.graph {
+                Some(graph) => {
+                    visit(self.edge(node, graph.service));
+                    graph.visit_edges(visit);
+                }
+                None => {
+                    // Fields that are not expanded belong to this service alone
+                    let leaf = format!("{}.{}", self.service, node.dependency.field);
+                    visit(format!("{} [label={}];", dot_id(&leaf), dot_id(node.dependency.type_name)));
+                    visit(self.edge(node, &leaf));
+                }
+            }
+        }
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"service\":");
+        write_json_string(json, self.service);
+        json.push_str(",\"dependencies\":[");
+        for (index, node) in self.dependencies.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"field\":");
+            write_json_string(json, node.dependency.field);
+            json.push_str(",\"type\":");
+            write_json_string(json, node.dependency.type_name);
+            let _ = write!(json, ",\"kind\":\"{}\",\"graph\":", node.dependency.kind.as_str());
+            match &node.graph {
+                Some(graph) => graph.write_json(json),
+                None => json.push_str("null"),
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+    }
+}
+
+fn dot_id(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A service that can describe how it is wired, implemented for every `#[builder]` struct.
+pub trait DependencyInfo {
+    fn graph(&self) -> DependencyGraph;
+}
+
+impl<T: DependencyInfo + ?Sized> DependencyInfo for Arc<T> {
+    fn graph(&self) -> DependencyGraph {
+        (**self).graph()
+    }
+}
+
+impl<T: DependencyInfo + ?Sized> DependencyInfo for Box<T> {
+    fn graph(&self) -> DependencyGraph {
+        (**self).graph()
+    }
+}
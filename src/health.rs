@@ -0,0 +1,169 @@
+//! Health and readiness reporting for composed services.
+//!
+//! Structs marked `#[builder(health)]` get a `health()` method that checks every field
+//! marked `#[builder(health)]`, and implement [`HealthCheck`] themselves so reports nest.
+
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Status of a single check, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+/// The result of a health check, including the results of any nested components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health {
+    pub status: HealthStatus,
+    pub message: Option<String>,
+    pub components: Vec<ComponentHealth>,
+}
+
+/// The health of a named component, along with how long its check took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub latency: Duration,
+    pub health: Health,
+}
+
+impl Health {
+    pub fn healthy() -> Self {
+        Health::new(HealthStatus::Healthy, None)
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Health::new(HealthStatus::Degraded, Some(message.into()))
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Health::new(HealthStatus::Unhealthy, Some(message.into()))
+    }
+
+    fn new(status: HealthStatus, message: Option<String>) -> Self {
+        Health {
+            status,
+            message,
+            components: Vec::new(),
+        }
+    }
+
+    /// Combines component results; the overall status is the worst component status.
+    pub fn aggregate(components: Vec<ComponentHealth>) -> Self {
+        let status = components
+            .iter()
+            .map(|component| component.health.status)
+            .max()
+            .unwrap_or(HealthStatus::Healthy);
+        Health {
+            status,
+            message: None,
+            components,
+        }
+    }
+
+    /// A service is ready to take traffic unless it is unhealthy; degraded services are ready.
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+
+    /// Renders the report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json, None);
+        json
+    }
+
+    fn write_json(&self, json: &mut String, component: Option<(&str, Duration)>) {
+        json.push('{');
+        if let Some((name, latency)) = component {
+            json.push_str("\"name\":");
+            write_json_string(json, name);
+            let _ = write!(json, ",\"latency_us\":{},", latency.as_micros());
+        }
+        let _ = write!(json, "\"status\":\"{}\",\"message\":", self.status.as_str());
+        match &self.message {
+            Some(message) => write_json_string(json, message),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"components\":[");
+        for (index, component) in self.components.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            component
+                .health
+                .write_json(json, Some((&component.name, component.latency)));
+        }
+        json.push_str("]}");
+    }
+}
+
+impl ComponentHealth {
+    /// Renders the component report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.health.write_json(&mut json, Some((&self.name, self.latency)));
+        json
+    }
+}
+
+pub(crate) fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// A service that can report its own health.
+pub trait HealthCheck {
+    fn check_health(&self) -> Health;
+}
+
+impl<T: HealthCheck + ?Sized> HealthCheck for Arc<T> {
+    fn check_health(&self) -> Health {
+        (**self).check_health()
+    }
+}
+
+impl<T: HealthCheck + ?Sized> HealthCheck for Box<T> {
+    fn check_health(&self) -> Health {
+        (**self).check_health()
+    }
+}
+
+/// Runs the check for `component` and records how long it took.
+pub fn check(name: &str, component: &dyn HealthCheck) -> ComponentHealth {
+    let started = Instant::now();
+    let health = component.check_health();
+    ComponentHealth {
+        name: name.to_string(),
+        latency: started.elapsed(),
+        health,
+    }
+}
@@ -0,0 +1,73 @@
+//! Values built on first use, backing `#[builder(lazy)]` fields.
+
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use crate::error::BuildError;
+
+type Factory<T> = Box<dyn Fn() -> Result<T, BuildError> + Send + Sync>;
+
+/// A value created by its factory the first time it is requested.
+///
+/// The factory runs at most once successfully; if it fails, the error is returned and the next
+/// request runs it again.
+pub struct Lazy<T> {
+    cell: OnceLock<T>,
+    init_lock: Mutex<()>,
+    factory: Factory<T>,
+}
+
+impl<T> Lazy<T> {
+    pub fn new<F>(factory: F) -> Self
+    where
+        F: Fn() -> Result<T, BuildError> + Send + Sync + 'static,
+    {
+        Lazy {
+            cell: OnceLock::new(),
+            init_lock: Mutex::new(()),
+            factory: Box::new(factory),
+        }
+    }
+
+    /// An already initialized value.
+    pub fn with_value(value: T) -> Self {
+        Lazy {
+            cell: OnceLock::from(value),
+            init_lock: Mutex::new(()),
+            factory: Box::new(|| unreachable!("initialized lazy values never run their factory")),
+        }
+    }
+
+    /// Returns the value, running the factory if this is the first successful request.
+    pub fn get(&self) -> Result<&T, BuildError> {
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+        // Only one caller runs the factory; the others wait and see its value
+        let _guard = self.init_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+        let value = (self.factory)()?;
+        Ok(self.cell.get_or_init(|| value))
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.cell.get().is_some()
+    }
+}
+
+impl<T: Default> Default for Lazy<T> {
+    fn default() -> Self {
+        Lazy::new(|| Ok(T::default()))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cell.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<uninitialized>)"),
+        }
+    }
+}
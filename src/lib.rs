@@ -52,12 +52,158 @@
 //! - `#[builder(getter)]`: Generates a getter method for the field
 //! - `#[builder(setter)]`: Generates a setter method for the field
 //! - Both can be combined: `#[builder(getter, setter)]`
+//! - `#[builder(default = "expression")]`: Uses the expression when the field is not set. The
+//!   expression can refer to other fields by name, e.g. `"port + 1"`.
+//! - `#[builder(factory = "make_client(&timeout, &base_url)")]`: Computes the field in `build()`
+//!   from an expression returning `Result<T, E>`. The expression can borrow other fields by name
+//!   and runs after them; an `Err` is reported as `BuildError::InitializationError`. Factory
+//!   fields have no builder method unless `overridable` is also given.
+//! - `#[builder(lazy)]`: Stores the field as a [`lazy::Lazy`] value created on first use. The
+//!   builder method takes a factory closure (`try_` builder methods take a fallible one), and the
+//!   generated `get_` getter creates the value once and returns `&T`. `try_get_` returns
+//!   `BuildError::InitializationError` instead of panicking when the factory fails.
+//! - `#[builder(decorate = "with_logging")]`: Passes the built value through a
+//!   `Fn(T) -> T` wrapper, e.g. `Fn(Arc<dyn Mailer>) -> Arc<dyn Mailer>` returning a logging proxy.
+//!   The attribute can be repeated; the first decorator wraps the value first. Decorators also
+//!   apply to defaults, factory results and test overrides.
+//! - `#[builder(delegate(UserRepository))]`: Implements the trait for the struct by forwarding
+//!   every method to the field. Methods listed in
+//!   `delegate(UserRepository, except(find = "Self::cached_find"))` call the given function with
+//!   the same arguments instead. The trait must be marked [`delegatable`].
+//! - `#[builder(weak)]`: For `Weak<T>` back-references, which default to `Weak::new()`. The
+//!   generated `get_` getter upgrades the reference and returns `Option<Arc<T>>`.
+//! - `#[builder(swappable)]`: For `Arc<T>` fields that are replaced at runtime, such as rotated
+//!   credentials. The field is stored as a [`swap::Swappable`], read with the generated
+//!   `load_` accessor and replaced through `&self` with `swap_`, which returns the previous
+//!   service. Callers holding a loaded `Arc` keep using it until they load again.
+//! - `#[builder(setter(check = "positive"))]`: Runs a `fn(&T) -> Result<(), E>` on the value the
+//!   struct is built with and on the new value before the `set_` setter stores it. Both fail with
+//!   `BuildError::ConfigurationError`, and the setter leaves the field unchanged.
+//! - `#[builder(observe)]`: Generates a `set_` setter that calls the field's listeners with the
+//!   old and new value after updating it. Listeners are registered through `&self` with
+//!   `subscribe_`, which returns an [`observe::Subscription`] for `unsubscribe_`.
+//!
+//! Services that refer to each other are built in two phases with `build_cyclic()`: the fields
+//! are resolved first, then the service is placed in an `Arc` with `Arc::new_cyclic` and its
+//! `Weak<Self>` fields are pointed at it. `build_cyclic_with(|service, weak| ...)` also hands the
+//! `Weak` to the caller, e.g. to set it on a child service.
 //!
 //! Generated methods follow these naming conventions:
 //! - Getters: `get_field_name() -> &FieldType`
 //! - Setters: `set_field_name(value: FieldType)`
+//!
+//! # Struct Attributes
+//!
+//! Options can also be passed to the `builder` attribute on the struct itself:
+//!
+//! - `#[builder(init = "Self::init")]`: Runs a `fn(&mut Self) -> Result<(), E>` hook after the
+//!   struct is assembled. An `Err` is reported as `BuildError::InitializationError`.
+//! - `#[builder(invariant = "Self::validate")]`: Checks a `fn(&Self) -> Result<(), E>` after the
+//!   `init` hook and after every setter call. Setters then return `Result<(), BuildError>` and put
+//!   the old value back if the check fails. Failures are `BuildError::ConfigurationError`.
+//! - `#[builder(update)]`: Generates `update(|builder| ...)` for changing several fields at once.
+//!   The closure gets a builder holding clones of the current values, which is built as usual,
+//!   so factories, the `init` hook and the invariant run again. The struct is replaced only if
+//!   the build succeeds. Skipped fields and listeners of observed fields carry over before the
+//!   `init` hook and invariant run. Field checks, decorators and listeners only see the fields
+//!   the closure sets, and overridable factories keep a value only if the closure sets it.
+//!   Builder fields must implement `Clone`; lazy fields are not supported.
+//! - `#[builder(async)]`: Makes `build()` and `build_with_defaults()` return futures. Fields can
+//!   use `#[builder(async_factory = "connect(&url)")]`, whose future is awaited, and the `init`
+//!   hook is awaited as well. Only `std::future` is used, so any executor can drive the build.
+//! - `#[builder(async, concurrent)]`: Awaits async factories that do not depend on each other
+//!   together, using [`future::join`].
+//! - `#[builder(lifecycle)]`: Generates `start_all()` and `stop_all()`, which start every field
+//!   marked `#[builder(lifecycle)]` in dependency order and stop them in reverse. See
+//!   [`lifecycle::Lifecycle`].
+//! - `#[builder(health)]`: Generates `health()`, which checks every field marked
+//!   `#[builder(health)]` and returns a [`health::Health`] report with per-field status,
+//!   latency and messages. See [`health::HealthCheck`].
+//! - `#[builder(global)]`: Generates `init_global(builder)`, which builds the process-wide
+//!   instance, and `global()`/`try_global()` returning `&'static Self`. Initializing twice returns
+//!   `BuildError::ConfigurationError`. In `#[cfg(test)]` code, `override_global(service)` replaces
+//!   the instance until the returned guard is dropped. See [`global::GlobalSlot`].
+//! - `#[builder(scope = "singleton")]`: Declares how long a [`container::Container`] reuses
+//!   instances of the struct: `"singleton"`, `"transient"` or `"scoped"`.
+//!
+//! # Dependency Graphs
+//!
+//! Every `#[builder]` struct gets a `dependency_info()` function listing its fields, their types
+//! and how the builder sets them (required, defaulted, optional, injected, ...), and implements
+//! [`graph::DependencyInfo`]. Its `graph()` method expands fields marked `#[builder(graph)]` into
+//! their own dependencies, and the resulting [`graph::DependencyGraph`] renders with `to_dot()`
+//! for Graphviz or `to_json()`.
+//!
+//! # Service Registry
+//!
+//! [`registry::ServiceRegistry`] stores shared services such as `Arc<dyn UserRepository>` at
+//! runtime, keyed by type and an optional name, for applications that need one place to look
+//! up "the" instance of a service.
+//!
+//! Several implementations of one trait, such as a local and a distributed `Arc<dyn Cache>`, are
+//! told apart by registering them under qualifiers with `register_named`. A failed lookup lists
+//! the qualifiers the type is registered under.
+//!
+//! Fields marked `#[builder(inject)]` (or `#[builder(inject(qualifier = "local"))]`) can be filled
+//! from a registry by their type with the generated `fill_from(&registry)` and
+//! `build_from(&registry)` builder methods. Fields that were set explicitly are left untouched,
+//! and every service that cannot be resolved is reported in a single
+//! `BuildError::MissingDependency`.
+//!
+//! Plugin-style services are bound with `register_many`, optionally with a priority, and
+//! `#[builder(inject_all)]` fields of type `Vec<Arc<dyn Trait>>` receive all of them, highest
+//! priority first and otherwise in registration order.
+//!
+//! # Test Overrides
+//!
+//! With the `test-overrides` feature, integration tests can build the real application but swap
+//! one service for a mock with `overrides::Overrides`. While an override set is installed on the
+//! current thread, generated builders use it for `Arc<T>` fields before their configured value,
+//! default or factory, and registry and container lookups return it too. Overrides are keyed by
+//! type and optional name, so `override_named` replaces a single qualifier. Dropping the guard
+//! panics if an override was never used, which catches overrides left behind after the code
+//! stopped depending on them. Without the feature, no override checks are compiled in.
+//!
+//! # Service Graphs
+//!
+//! The [`services!`] macro declares an application's services and their providers in one place
+//! and generates a struct holding all of them, built in dependency order. Providers are either
+//! expressions that refer to other services by name, or `auto`, which builds a `#[builder]`
+//! struct with its generated builder. `auto` sets each builder field from the declared service of
+//! the same type, as written; `auto(field: service, ...)` names the service for a field instead,
+//! e.g. when several services share a type. Required fields without a provider, services of the
+//! same type competing for a field, undeclared services and dependency cycles are compile errors.
+//! `auto` services must be declared in the same crate as the `services!` invocation.
+//!
+//! # Scopes
+//!
+//! [`container::Container`] builds services on demand from registered providers and reuses them
+//! according to their [`container::Scope`]. Singletons are shared by the whole container,
+//! transient services are built on every resolution, and scoped services are built once per
+//! [`container::ServiceScope`] created with `create_scope()`, such as one `UnitOfWork` per
+//! request. A singleton that depends on a scoped service fails with
+//! `BuildError::ScopeViolation` instead of keeping a request's instance alive forever.
+//!
+//! The container tracks the services it is building, so providers that depend on each other
+//! fail with `BuildError::CircularDependency` naming the whole cycle, e.g.
+//! `AuthService -> UserService -> AuthService`, instead of overflowing the stack.
 
+pub mod container;
 pub mod error;
+pub mod future;
+pub mod global;
+pub mod graph;
+pub mod health;
+pub mod lazy;
+pub mod lifecycle;
+pub mod observe;
+#[cfg(feature = "test-overrides")]
+pub mod overrides;
 pub mod prelude;
+pub mod registry;
+pub mod swap;
+
+pub use service_builder_macro::{builder, delegatable, services};
 
-pub use service_builder_macro::builder;
\ No newline at end of file
+#[doc(hidden)]
+pub use service_builder_macro::{__delegate_impl, __services_impl};
\ No newline at end of file
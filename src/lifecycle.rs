@@ -0,0 +1,92 @@
+//! Start and stop hooks for built services.
+//!
+//! Structs marked `#[builder(lifecycle)]` get `start_all()` and `stop_all()` methods that
+//! drive every field marked `#[builder(lifecycle)]`, and implement [`Lifecycle`] themselves
+//! so composed services can be nested.
+
+use std::sync::Arc;
+
+use crate::error::{BuildError, ComponentFailure, LifecycleError, LifecyclePhase};
+
+/// Result of a single start or stop hook.
+pub type LifecycleResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// A service that needs to be started before use and stopped on shutdown.
+pub trait Lifecycle {
+    fn start(&self) -> LifecycleResult {
+        Ok(())
+    }
+
+    fn stop(&self) -> LifecycleResult {
+        Ok(())
+    }
+}
+
+impl<T: Lifecycle + ?Sized> Lifecycle for Arc<T> {
+    fn start(&self) -> LifecycleResult {
+        (**self).start()
+    }
+
+    fn stop(&self) -> LifecycleResult {
+        (**self).stop()
+    }
+}
+
+impl<T: Lifecycle + ?Sized> Lifecycle for Box<T> {
+    fn start(&self) -> LifecycleResult {
+        (**self).start()
+    }
+
+    fn stop(&self) -> LifecycleResult {
+        (**self).stop()
+    }
+}
+
+/// Starts `components` in order.
+///
+/// If one fails, the components started before it are stopped again in reverse order,
+/// and the start failure is returned together with any failures from that rollback.
+pub fn start_all(components: &[(&str, &dyn Lifecycle)]) -> Result<(), BuildError> {
+    for (index, (name, component)) in components.iter().enumerate() {
+        if let Err(error) = component.start() {
+            let mut failures = vec![ComponentFailure {
+                component: name.to_string(),
+                phase: LifecyclePhase::Start,
+                error,
+            }];
+            failures.extend(stop_each(&components[..index]));
+            return Err(BuildError::LifecycleFailed(LifecycleError {
+                phase: LifecyclePhase::Start,
+                failures,
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Stops `components` in reverse order, attempting every component even if some fail.
+pub fn stop_all(components: &[(&str, &dyn Lifecycle)]) -> Result<(), BuildError> {
+    let failures = stop_each(components);
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(BuildError::LifecycleFailed(LifecycleError {
+            phase: LifecyclePhase::Stop,
+            failures,
+        }))
+    }
+}
+
+fn stop_each(components: &[(&str, &dyn Lifecycle)]) -> Vec<ComponentFailure> {
+    components
+        .iter()
+        .rev()
+        .filter_map(|(name, component)| {
+            component.stop().err().map(|error| ComponentFailure {
+                component: name.to_string(),
+                phase: LifecyclePhase::Stop,
+                error,
+            })
+        })
+        .collect()
+}
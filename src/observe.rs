@@ -0,0 +1,79 @@
+//! Change listeners, backing `#[builder(observe)]` fields.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type Listener<T> = Arc<dyn Fn(&T, &T) + Send + Sync>;
+
+/// Identifies a listener registered with [`Observers::subscribe`], for unsubscribing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subscription(u64);
+
+/// The listeners notified when an observed field is set.
+///
+/// Listeners are called in subscription order with the old and new value, after the field
+/// has been updated. A listener may subscribe or unsubscribe others; the change applies from
+/// the next notification.
+pub struct Observers<T> {
+    listeners: Mutex<Vec<(Subscription, Listener<T>)>>,
+    next_id: AtomicU64,
+}
+
+impl<T> Observers<T> {
+    pub fn new() -> Self {
+        Observers {
+            listeners: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    pub fn subscribe<F>(&self, listener: F) -> Subscription
+    where
+        F: Fn(&T, &T) + Send + Sync + 'static,
+    {
+        let subscription = Subscription(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.lock().push((subscription, Arc::new(listener)));
+        subscription
+    }
+
+    /// Removes a listener, returning `false` if it was not subscribed.
+    pub fn unsubscribe(&self, subscription: Subscription) -> bool {
+        let mut listeners = self.lock();
+        let before = listeners.len();
+        listeners.retain(|(id, _)| *id != subscription);
+        listeners.len() != before
+    }
+
+    /// Calls every listener with the old and new value.
+    pub fn notify(&self, old: &T, new: &T) {
+        let listeners: Vec<Listener<T>> = self.lock().iter().map(|(_, listener)| Arc::clone(listener)).collect();
+        for listener in listeners {
+            listener(old, new);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(Subscription, Listener<T>)>> {
+        self.listeners.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T> Default for Observers<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Observers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers").field("listeners", &self.len()).finish()
+    }
+}
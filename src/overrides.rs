@@ -0,0 +1,192 @@
+//! Test overrides that replace services while the real application graph is built.
+//!
+//! Only available with the `test-overrides` feature, which is meant to be enabled for
+//! dev-dependencies; without it, generated builders and lookups do not check for overrides.
+//!
+//! An override set maps a service type, and optionally a name, to the instance to use instead.
+//! While it is installed, generated builders use it for `Arc<T>` fields before their configured
+//! value, default or factory, and [`ServiceRegistry`](crate::registry::ServiceRegistry) and
+//! [`Container`](crate::container::Container) lookups return it instead of the registered service:
+//!
+//! ```ignore
+//! let _overrides = Overrides::new()
+//!     .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+//!     .override_named::<dyn Cache>("local", Arc::new(MemoryCache::default()))
+//!     .install();
+//!
+//! let app = AppServices::build()?; // uses the mock gateway and the in-memory local cache
+//! ```
+//!
+//! An unnamed override replaces unnamed lookups and fields without an `inject` name; a named one
+//! replaces lookups of that name and fields injected under it. Services bound with
+//! `register_many` are not overridden, so tests register the list they need.
+//!
+//! Overrides are installed for the current thread only, so tests running in parallel do not
+//! see each other's overrides. An override that is never used usually means the test no longer
+//! tests what it claims to, so dropping the guard panics if any override was not used;
+//! [`OverrideGuard::verify`] reports this as an error instead.
+
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::BuildError;
+
+struct Override {
+    type_name: &'static str,
+    name: Option<String>,
+    // Always an `Arc<T>` for the `T` it is keyed by. Overrides never leave their thread, so
+    // services that are not `Send` or `Sync` can be overridden too
+    service: Box<dyn Any>,
+    used: Cell<bool>,
+}
+
+/// A set of services to use instead of the real ones, keyed by type and optional name.
+#[derive(Default)]
+pub struct Overrides {
+    services: HashMap<(TypeId, Option<String>), Override>,
+}
+
+struct Installed {
+    id: u64,
+    overrides: Overrides,
+}
+
+thread_local! {
+    static INSTALLED: RefCell<Vec<Installed>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+impl Overrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `service` wherever an unnamed `T` is built or looked up, replacing any earlier
+    /// unnamed override of `T`.
+    pub fn override_with<T>(self, service: Arc<T>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        self.insert(None, service)
+    }
+
+    /// Uses `service` wherever the `T` called `name` is built or looked up, replacing any earlier
+    /// override of that name. Other names and the unnamed `T` are unaffected.
+    pub fn override_named<T>(self, name: impl Into<String>, service: Arc<T>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        self.insert(Some(name.into()), service)
+    }
+
+    fn insert<T>(mut self, name: Option<String>, service: Arc<T>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        self.services.insert(
+            (TypeId::of::<T>(), name.clone()),
+            Override {
+                type_name: type_name::<T>(),
+                name,
+                service: Box::new(service),
+                used: Cell::new(false),
+            },
+        );
+        self
+    }
+
+    /// Installs the overrides on the current thread until the returned guard is dropped.
+    ///
+    /// Sets installed later take precedence over earlier ones.
+    pub fn install(self) -> OverrideGuard {
+        let id = NEXT_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        });
+        INSTALLED.with(|installed| {
+            installed.borrow_mut().push(Installed { id, overrides: self });
+        });
+        OverrideGuard { id, verified: false }
+    }
+}
+
+/// Keeps an override set installed. Dropping it uninstalls the set and panics if any override
+/// was never used.
+#[must_use = "overrides are uninstalled when the guard is dropped"]
+pub struct OverrideGuard {
+    id: u64,
+    verified: bool,
+}
+
+impl OverrideGuard {
+    /// Uninstalls the overrides, returning `BuildError::ConfigurationError` naming every override
+    /// that was never used.
+    pub fn verify(mut self) -> Result<(), BuildError> {
+        self.verified = true;
+        let unused = self.uninstall();
+        if unused.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildError::ConfigurationError(format!(
+                "unused overrides: {}",
+                unused.join(", ")
+            )))
+        }
+    }
+
+    fn uninstall(&self) -> Vec<String> {
+        let removed = INSTALLED.with(|installed| {
+            let mut installed = installed.borrow_mut();
+            let index = installed.iter().position(|set| set.id == self.id)?;
+            Some(installed.remove(index))
+        });
+        let mut unused: Vec<String> = removed
+            .map(|set| {
+                set.overrides
+                    .services
+                    .into_values()
+                    .filter(|service| !service.used.get())
+                    .map(|service| match service.name {
+                        Some(name) => format!("{} named \"{}\"", service.type_name, name),
+                        None => service.type_name.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        unused.sort_unstable();
+        unused
+    }
+}
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        if self.verified {
+            return;
+        }
+        let unused = self.uninstall();
+        if !unused.is_empty() && !std::thread::panicking() {
+            panic!("unused overrides: {}", unused.join(", "));
+        }
+    }
+}
+
+/// Returns the installed override for `T` and `name`, if any, and marks it as used.
+///
+/// Called by generated builders; registry and container lookups check it too.
+pub fn lookup<T>(name: Option<&str>) -> Option<Arc<T>>
+where
+    T: ?Sized + 'static,
+{
+    let key = (TypeId::of::<T>(), name.map(str::to_string));
+    INSTALLED.with(|installed| {
+        let installed = installed.borrow();
+        installed.iter().rev().find_map(|set| {
+            let service = set.overrides.services.get(&key)?;
+            service.used.set(true);
+            service.service.downcast_ref::<Arc<T>>().cloned()
+        })
+    })
+}
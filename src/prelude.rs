@@ -1 +1 @@
-pub use crate::builder;
\ No newline at end of file
+pub use crate::{builder, delegatable, services};
\ No newline at end of file
@@ -0,0 +1,253 @@
+//! A runtime registry of shared services, keyed by type and an optional name.
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::BuildError;
+
+type Key = (TypeId, Option<String>);
+
+struct Entry {
+    type_name: &'static str,
+    // Always an `Arc<T>` for the `T` in the key, so unsized services can be stored too
+    service: Arc<dyn Any + Send + Sync>,
+}
+
+/// One of several services bound to the same type with `register_many`.
+struct Binding {
+    priority: i32,
+    entry: Entry,
+}
+
+/// Holds the application's shared services, such as "the" `Arc<dyn UserRepository>`.
+///
+/// Services are registered as `Arc<T>` and looked up by `T`, which may be a trait object:
+///
+/// ```ignore
+/// let mut registry = ServiceRegistry::new();
+/// registry.register::<dyn UserRepository>(Arc::new(PgUserRepository::new()));
+///
+/// let repository: Arc<dyn UserRepository> = registry.get::<dyn UserRepository>()?;
+/// ```
+#[derive(Default)]
+pub struct ServiceRegistry {
+    services: HashMap<Key, Entry>,
+    // Kept in registration order; `get_all` sorts by priority
+    bindings: HashMap<TypeId, Vec<Binding>>,
+}
+
+impl ServiceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `service` as the unnamed `T`, replacing any previous registration.
+    pub fn register<T>(&mut self, service: Arc<T>) -> &mut Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.insert(None, service);
+        self
+    }
+
+    /// Registers `service` as the `T` called `name`, replacing any previous registration.
+    pub fn register_named<T>(&mut self, name: impl Into<String>, service: Arc<T>) -> &mut Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.insert(Some(name.into()), service);
+        self
+    }
+
+    /// Adds `service` to the services bound to `T`, next to those registered before it.
+    ///
+    /// Multi-bindings are separate from `register`, and are collected with [`Self::get_all`].
+    pub fn register_many<T>(&mut self, service: Arc<T>) -> &mut Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.register_many_with_priority(0, service)
+    }
+
+    /// Adds `service` to the services bound to `T`. Services with a higher priority come first
+    /// in [`Self::get_all`]; those with equal priority keep their registration order.
+    pub fn register_many_with_priority<T>(&mut self, priority: i32, service: Arc<T>) -> &mut Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.bindings.entry(TypeId::of::<T>()).or_default().push(Binding {
+            priority,
+            entry: Entry {
+                type_name: type_name::<T>(),
+                service: Arc::new(service),
+            },
+        });
+        self
+    }
+
+    /// Returns every service bound to `T` with `register_many`, highest priority first and
+    /// otherwise in registration order. Returns an empty list if there are none. Test overrides
+    /// do not apply to these bindings.
+    pub fn get_all<T>(&self) -> Vec<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let mut bindings: Vec<&Binding> = self
+            .bindings
+            .get(&TypeId::of::<T>())
+            .map(|bindings| bindings.iter().collect())
+            .unwrap_or_default();
+        bindings.sort_by_key(|binding| std::cmp::Reverse(binding.priority));
+        bindings
+            .into_iter()
+            .filter_map(|binding| binding.entry.service.downcast_ref::<Arc<T>>())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the unnamed `T`, or `BuildError::MissingDependency` naming the type and any
+    /// qualifiers it is registered under. An installed unnamed test override of `T` takes precedence.
+    pub fn get<T>(&self) -> Result<Arc<T>, BuildError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        #[cfg(feature = "test-overrides")]
+        if let Some(service) = crate::overrides::lookup::<T>(None) {
+            return Ok(service);
+        }
+        self.lookup::<T>(None)
+            .ok_or_else(|| self.missing::<T>(type_name::<T>().to_string()))
+    }
+
+    /// Returns the `T` called `name`, or `BuildError::MissingDependency` naming the type and the
+    /// qualifiers it is registered under. An installed test override of `T` named `name` takes
+    /// precedence.
+    pub fn get_named<T>(&self, name: &str) -> Result<Arc<T>, BuildError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        #[cfg(feature = "test-overrides")]
+        if let Some(service) = crate::overrides::lookup::<T>(Some(name)) {
+            return Ok(service);
+        }
+        self.lookup::<T>(Some(name))
+            .ok_or_else(|| self.missing::<T>(format!("{} named \"{}\"", type_name::<T>(), name)))
+    }
+
+    /// Returns the names, or qualifiers, that `T` is registered under, sorted.
+    pub fn qualifiers<T: ?Sized + 'static>(&self) -> Vec<&str> {
+        let mut qualifiers: Vec<&str> = self
+            .services
+            .keys()
+            .filter(|(id, _)| *id == TypeId::of::<T>())
+            .filter_map(|(_, name)| name.as_deref())
+            .collect();
+        qualifiers.sort_unstable();
+        qualifiers
+    }
+
+    pub fn contains<T: ?Sized + 'static>(&self) -> bool {
+        self.services.contains_key(&(TypeId::of::<T>(), None))
+    }
+
+    pub fn contains_named<T: ?Sized + 'static>(&self, name: &str) -> bool {
+        self.services
+            .contains_key(&(TypeId::of::<T>(), Some(name.to_string())))
+    }
+
+    /// The number of registered services, counting each multi-binding.
+    pub fn len(&self) -> usize {
+        self.services.len() + self.bindings.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert<T>(&mut self, name: Option<String>, service: Arc<T>)
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.services.insert(
+            (TypeId::of::<T>(), name),
+            Entry {
+                type_name: type_name::<T>(),
+                service: Arc::new(service),
+            },
+        );
+    }
+
+    fn missing<T: ?Sized + 'static>(&self, service: String) -> BuildError {
+        let qualifiers = self.qualifiers::<T>();
+        if qualifiers.is_empty() {
+            return BuildError::MissingDependency(service);
+        }
+        let qualifiers: Vec<String> = qualifiers.iter().map(|name| format!("\"{}\"", name)).collect();
+        BuildError::MissingDependency(format!("{} (available qualifiers: {})", service, qualifiers.join(", ")))
+    }
+
+    fn lookup<T>(&self, name: Option<&str>) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.services
+            .get(&(TypeId::of::<T>(), name.map(str::to_string)))
+            .and_then(|entry| entry.service.downcast_ref::<Arc<T>>())
+            .cloned()
+    }
+}
+
+impl fmt::Debug for ServiceRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<String> = self
+            .services
+            .iter()
+            .map(|((_, name), entry)| match name {
+                Some(name) => format!("{} named \"{}\"", entry.type_name, name),
+                None => entry.type_name.to_string(),
+            })
+            .chain(self.bindings.values().filter(|bindings| !bindings.is_empty()).map(|bindings| {
+                format!("{} ({} bindings)", bindings[0].entry.type_name, bindings.len())
+            }))
+            .collect();
+        entries.sort();
+        f.debug_struct("ServiceRegistry")
+            .field("services", &entries)
+            .finish()
+    }
+}
+
+/// A field type that generated `fill_from`/`build_from` methods can resolve from a
+/// [`ServiceRegistry`], as used by `#[builder(inject)]` fields.
+pub trait Injectable: Sized {
+    fn resolve(registry: &ServiceRegistry, name: Option<&str>) -> Result<Self, BuildError>;
+}
+
+impl<T> Injectable for Arc<T>
+where
+    T: ?Sized + Send + Sync + 'static,
+{
+    fn resolve(registry: &ServiceRegistry, name: Option<&str>) -> Result<Self, BuildError> {
+        match name {
+            Some(name) => registry.get_named::<T>(name),
+            None => registry.get::<T>(),
+        }
+    }
+}
+
+/// A field type that generated `fill_from`/`build_from` methods fill with every multi-binding
+/// in a [`ServiceRegistry`], as used by `#[builder(inject_all)]` fields.
+pub trait InjectAll: Sized {
+    fn resolve_all(registry: &ServiceRegistry) -> Self;
+}
+
+impl<T> InjectAll for Vec<Arc<T>>
+where
+    T: ?Sized + Send + Sync + 'static,
+{
+    fn resolve_all(registry: &ServiceRegistry) -> Self {
+        registry.get_all::<T>()
+    }
+}
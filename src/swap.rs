@@ -0,0 +1,69 @@
+//! Shared services that can be replaced at runtime, backing `#[builder(swappable)]` fields.
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::graph::{DependencyGraph, DependencyInfo};
+use crate::health::{Health, HealthCheck};
+use crate::lifecycle::{Lifecycle, LifecycleResult};
+
+/// An `Arc<T>` that can be replaced through a shared reference.
+///
+/// Readers get the current `Arc` with [`Swappable::load`] and keep using it even if it is
+/// swapped out afterwards, so in-flight work finishes on the old service.
+pub struct Swappable<T: ?Sized> {
+    current: RwLock<Arc<T>>,
+}
+
+impl<T: ?Sized> Swappable<T> {
+    pub fn new(service: Arc<T>) -> Self {
+        Swappable {
+            current: RwLock::new(service),
+        }
+    }
+
+    /// Returns the current service.
+    pub fn load(&self) -> Arc<T> {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Replaces the service, returning the previous one.
+    pub fn swap(&self, service: Arc<T>) -> Arc<T> {
+        let mut current = self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        std::mem::replace(&mut *current, service)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Swappable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Swappable").field(&self.load()).finish()
+    }
+}
+
+impl<T: Lifecycle + ?Sized> Lifecycle for Swappable<T> {
+    fn start(&self) -> LifecycleResult {
+        self.load().start()
+    }
+
+    fn stop(&self) -> LifecycleResult {
+        self.load().stop()
+    }
+}
+
+impl<T: HealthCheck + ?Sized> HealthCheck for Swappable<T> {
+    fn check_health(&self) -> Health {
+        self.load().check_health()
+    }
+}
+
+impl<T: DependencyInfo + ?Sized> DependencyInfo for Swappable<T> {
+    fn graph(&self) -> DependencyGraph {
+        self.load().graph()
+    }
+}
@@ -0,0 +1,167 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+// Minimal single-threaded executor, enough to drive the generated futures
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+// Returns `Pending` once before completing, so interleaving is observable
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+async fn connect(log: &Log, name: &str) -> Result<String, String> {
+    log.lock().unwrap().push(format!("{} started", name));
+    YieldOnce(false).await;
+    log.lock().unwrap().push(format!("{} finished", name));
+    Ok(format!("{} connection", name))
+}
+
+async fn load_keys(url: &str) -> Result<Vec<String>, String> {
+    YieldOnce(false).await;
+    if url.is_empty() {
+        return Err("no key server".to_string());
+    }
+    Ok(vec![format!("key from {}", url)])
+}
+
+#[builder(async, init = "Self::warm_up")]
+struct Database {
+    url: String,
+    #[builder(async_factory = "load_keys(&url)")]
+    keys: Vec<String>,
+    #[builder(default)]
+    warmed: bool,
+}
+
+impl Database {
+    async fn warm_up(&mut self) -> Result<(), String> {
+        YieldOnce(false).await;
+        self.warmed = true;
+        Ok(())
+    }
+}
+
+#[builder(async)]
+struct SequentialServices {
+    #[builder(default)]
+    log: Log,
+    #[builder(async_factory = "connect(&log, \"db\")")]
+    db: String,
+    #[builder(async_factory = "connect(&log, \"cache\")")]
+    cache: String,
+}
+
+#[builder(async, concurrent)]
+struct ConcurrentServices {
+    #[builder(default)]
+    log: Log,
+    #[builder(async_factory = "connect(&log, \"db\")")]
+    db: String,
+    #[builder(async_factory = "connect(&log, \"cache\")", overridable)]
+    cache: String,
+    #[builder(factory = "Ok::<_, String>(format!(\"{} + {}\", db, cache))")]
+    summary: String,
+}
+
+#[test]
+fn test_async_factory_and_init_hook() {
+    let db = block_on(Database::builder().url("keys.local".to_string()).build()).unwrap();
+
+    assert_eq!(db.url, "keys.local");
+    assert_eq!(db.keys, vec!["key from keys.local".to_string()]);
+    assert!(db.warmed);
+}
+
+#[test]
+fn test_async_factory_error() {
+    let result = block_on(Database::builder().url(String::new()).build_with_defaults());
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::InitializationError(message)) => {
+            assert_eq!(message, "Database.keys: no key server");
+        }
+        _ => panic!("Expected InitializationError"),
+    }
+}
+
+#[test]
+fn test_async_factories_run_sequentially_by_default() {
+    let services = block_on(SequentialServices::builder().build()).unwrap();
+
+    assert_eq!(services.db, "db connection");
+    assert_eq!(services.cache, "cache connection");
+    assert_eq!(*services.log.lock().unwrap(), vec![
+        "db started", "db finished", "cache started", "cache finished",
+    ]);
+}
+
+#[test]
+fn test_independent_async_factories_run_concurrently() {
+    let services = block_on(ConcurrentServices::builder().build()).unwrap();
+
+    assert_eq!(services.db, "db connection");
+    assert_eq!(services.cache, "cache connection");
+    assert_eq!(services.summary, "db connection + cache connection");
+    assert_eq!(*services.log.lock().unwrap(), vec![
+        "db started", "cache started", "db finished", "cache finished",
+    ]);
+}
+
+#[test]
+fn test_concurrent_factory_override() {
+    let services = block_on(
+        ConcurrentServices::builder()
+            .cache("in-memory".to_string())
+            .build(),
+    )
+    .unwrap();
+
+    assert_eq!(services.summary, "db connection + in-memory");
+    assert_eq!(*services.log.lock().unwrap(), vec!["db started", "db finished"]);
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[test]
+fn test_concurrent_build_future_is_send() {
+    // Lets the build be spawned on multithreaded executors
+    let build = assert_send(ConcurrentServices::builder().build());
+    let services = block_on(build).unwrap();
+
+    assert_eq!(services.summary, "db connection + cache connection");
+}
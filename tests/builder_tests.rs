@@ -23,11 +23,11 @@ fn test_repository_builder() {
 
 #[test]
 fn test_cache_builder() {
-    let cache = Arc::new(MockCache::builder()
+    let cache: Arc<dyn Cache> = Arc::new(MockCache::builder()
         .value("test".to_string())
         .build()
         .unwrap());
-    assert!(!Arc::as_ptr(&cache).is_null());
+    assert_eq!(Arc::strong_count(&cache), 1);
 }
 
 #[test]
@@ -43,7 +43,7 @@ fn test_cache_initialization() {
         .value("test".to_string())
         .build()
         .unwrap());
-    assert!(!Arc::as_ptr(&cache).is_null());
+    assert_eq!(Arc::strong_count(&cache), 1);
 }
 
 #[test]
@@ -59,6 +59,10 @@ fn test_service_builder_success() {
         .cache(cache)
         .build();
     assert!(service.is_ok());
+
+    let service = service.unwrap();
+    assert!(service.repository.initialize().is_ok());
+    assert_eq!(Arc::strong_count(&service.cache), 1);
 }
 
 #[test]
@@ -79,3 +83,15 @@ fn test_empty_service_builder() {
     let service = EmptyService::builder().build();
     assert!(service.is_ok());
 }
+
+// Generated methods must not declare type parameters that shadow the struct's name
+#[builder]
+struct F {
+    value: u32,
+}
+
+#[test]
+fn test_struct_named_like_type_parameter() {
+    let service = F::builder().value(7).build().unwrap();
+    assert_eq!(service.value, 7);
+}
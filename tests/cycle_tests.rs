@@ -0,0 +1,132 @@
+use service_builder::builder;
+use service_builder::container::{Container, Scope};
+use service_builder::error::BuildError;
+use std::sync::Arc;
+
+#[builder(scope = "transient")]
+struct AuthService {
+    users: Arc<UserService>,
+}
+
+#[builder(scope = "transient")]
+struct UserService {
+    auth: Arc<AuthService>,
+}
+
+#[builder(scope = "singleton")]
+struct App {
+    auth: Arc<AuthService>,
+}
+
+trait Wired {
+    fn dependencies(&self) -> usize;
+}
+
+impl Wired for AuthService {
+    fn dependencies(&self) -> usize {
+        Arc::strong_count(&self.users)
+    }
+}
+
+impl Wired for UserService {
+    fn dependencies(&self) -> usize {
+        Arc::strong_count(&self.auth)
+    }
+}
+
+impl Wired for App {
+    fn dependencies(&self) -> usize {
+        Arc::strong_count(&self.auth)
+    }
+}
+
+fn container() -> Container {
+    let mut container = Container::new();
+    container
+        .register_service(|r| AuthService::builder().users(r.resolve()?).build())
+        .register_service(|r| UserService::builder().auth(r.resolve()?).build())
+        .register_service(|r| App::builder().auth(r.resolve()?).build());
+    container
+}
+
+fn cycle<T: Wired>(result: Result<Arc<T>, BuildError>) -> String {
+    match result {
+        Err(BuildError::CircularDependency(path)) => path,
+        Err(other) => panic!("Expected CircularDependency, got {:?}", other),
+        Ok(service) => panic!("Expected CircularDependency, resolved {} dependencies", service.dependencies()),
+    }
+}
+
+#[test]
+fn test_cycle_reports_full_path() {
+    let container = container();
+
+    assert_eq!(
+        cycle(container.resolve::<AuthService>()),
+        "cycle_tests::AuthService -> cycle_tests::UserService -> cycle_tests::AuthService"
+    );
+    assert_eq!(
+        cycle(container.resolve::<UserService>()),
+        "cycle_tests::UserService -> cycle_tests::AuthService -> cycle_tests::UserService"
+    );
+}
+
+#[test]
+fn test_cycle_path_starts_at_the_repeated_service() {
+    let container = container();
+
+    // `App` depends on the cycle but is not part of it
+    assert_eq!(
+        cycle(container.resolve::<App>()),
+        "cycle_tests::AuthService -> cycle_tests::UserService -> cycle_tests::AuthService"
+    );
+}
+
+#[test]
+fn test_singleton_depending_on_itself() {
+    trait Clock: Send + Sync {
+        fn now(&self) -> u64;
+    }
+    struct Delegating(Arc<dyn Clock>);
+    impl Clock for Delegating {
+        fn now(&self) -> u64 {
+            self.0.now()
+        }
+    }
+
+    let mut container = Container::new();
+    container.register::<dyn Clock, _>(Scope::Singleton, |r| {
+        Ok(Arc::new(Delegating(r.resolve::<dyn Clock>()?)))
+    });
+
+    let error = match container.resolve::<dyn Clock>() {
+        Err(error) => error,
+        Ok(clock) => panic!("Expected CircularDependency, resolved a clock at {}", clock.now()),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Circular dependency: dyn cycle_tests::test_singleton_depending_on_itself::Clock -> dyn cycle_tests::test_singleton_depending_on_itself::Clock"
+    );
+}
+
+#[test]
+fn test_shared_dependencies_are_not_cycles() {
+    #[builder(scope = "transient")]
+    struct Config {
+        port: u16,
+    }
+
+    #[builder(scope = "transient")]
+    struct Server {
+        config: Arc<Config>,
+        admin_config: Arc<Config>,
+    }
+
+    let mut container = Container::new();
+    container
+        .register_service(|_| Config::builder().port(8080).build())
+        .register_service(|r| Server::builder().config(r.resolve()?).admin_config(r.resolve()?).build());
+
+    let server = container.resolve::<Server>().unwrap();
+    assert_eq!(server.config.port, server.admin_config.port);
+}
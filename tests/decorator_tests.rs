@@ -0,0 +1,101 @@
+use service_builder::builder;
+use service_builder::overrides::Overrides;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+trait Greeter: Send + Sync {
+    fn greet(&self, name: &str) -> String;
+}
+
+struct English;
+
+impl Greeter for English {
+    fn greet(&self, name: &str) -> String {
+        format!("hello {}", name)
+    }
+}
+
+struct Logging(Arc<dyn Greeter>);
+
+impl Greeter for Logging {
+    fn greet(&self, name: &str) -> String {
+        format!("log({})", self.0.greet(name))
+    }
+}
+
+struct Counting {
+    inner: Arc<dyn Greeter>,
+    calls: AtomicU32,
+}
+
+impl Greeter for Counting {
+    fn greet(&self, name: &str) -> String {
+        let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("{} #{}", self.inner.greet(name), calls)
+    }
+}
+
+fn with_logging(inner: Arc<dyn Greeter>) -> Arc<dyn Greeter> {
+    Arc::new(Logging(inner))
+}
+
+fn with_counting(inner: Arc<dyn Greeter>) -> Arc<dyn Greeter> {
+    Arc::new(Counting { inner, calls: AtomicU32::new(0) })
+}
+
+fn english() -> Result<Arc<dyn Greeter>, String> {
+    Ok(Arc::new(English))
+}
+
+#[builder]
+struct FrontDesk {
+    #[builder(decorate = "with_counting", decorate = "with_logging")]
+    greeter: Arc<dyn Greeter>,
+    #[builder(factory = "english()", decorate = "with_logging")]
+    fallback: Arc<dyn Greeter>,
+    #[builder(default = "1", decorate = "|retries: u32| retries.max(3)")]
+    retries: u32,
+}
+
+#[test]
+fn test_decorators_apply_in_declaration_order() {
+    let desk = FrontDesk::builder()
+        .greeter(Arc::new(English))
+        .build()
+        .unwrap();
+
+    // `with_counting` wraps the service first, then `with_logging` wraps the result
+    assert_eq!(desk.greeter.greet("ada"), "log(hello ada #1)");
+    assert_eq!(desk.greeter.greet("ada"), "log(hello ada #2)");
+}
+
+#[test]
+fn test_decorators_apply_to_factories_and_defaults() {
+    let desk = FrontDesk::builder()
+        .greeter(Arc::new(English))
+        .retries(5)
+        .build()
+        .unwrap();
+
+    assert_eq!(desk.fallback.greet("bob"), "log(hello bob)");
+    assert_eq!(desk.retries, 5);
+    assert_eq!(FrontDesk::builder().greeter(Arc::new(English)).build().unwrap().retries, 3);
+}
+
+#[test]
+fn test_decorators_wrap_overridden_services() {
+    struct Mock;
+    impl Greeter for Mock {
+        fn greet(&self, _name: &str) -> String {
+            "mock".to_string()
+        }
+    }
+    let _overrides = Overrides::new()
+        .override_with::<dyn Greeter>(Arc::new(Mock))
+        .install();
+
+    let desk = FrontDesk::builder().build().unwrap();
+
+    assert_eq!(desk.greeter.greet("ada"), "log(mock #1)");
+    assert_eq!(desk.fallback.greet("ada"), "log(mock)");
+}
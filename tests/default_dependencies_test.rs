@@ -0,0 +1,61 @@
+use service_builder::builder;
+use std::path::PathBuf;
+
+#[builder]
+struct ServerConfig {
+    #[builder(default = "port + 1")]
+    metrics_port: u16,
+
+    #[builder(default = "8080")]
+    port: u16,
+
+    #[builder(default = "data_dir.join(\"cache\")")]
+    cache_dir: PathBuf,
+
+    data_dir: PathBuf,
+
+    #[builder(default = "format!(\"{}:{}\", host, port)")]
+    address: String,
+
+    #[builder(default = "\"localhost\".to_string()")]
+    host: String,
+}
+
+#[test]
+fn test_default_refers_to_defaulted_field() {
+    let config = ServerConfig::builder()
+        .data_dir(PathBuf::from("/var/lib/app"))
+        .build()
+        .unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.metrics_port, 8081);
+    assert_eq!(config.address, "localhost:8080");
+}
+
+#[test]
+fn test_default_refers_to_provided_field() {
+    let config = ServerConfig::builder()
+        .port(9000)
+        .data_dir(PathBuf::from("/srv/data"))
+        .build_with_defaults()
+        .unwrap();
+
+    assert_eq!(config.metrics_port, 9001);
+    assert_eq!(config.data_dir, PathBuf::from("/srv/data"));
+    assert_eq!(config.cache_dir, PathBuf::from("/srv/data/cache"));
+    assert_eq!(config.host, "localhost");
+}
+
+#[test]
+fn test_provided_value_wins_over_dependent_default() {
+    let config = ServerConfig::builder()
+        .data_dir(PathBuf::from("/srv/data"))
+        .metrics_port(1234)
+        .cache_dir(PathBuf::from("/tmp/cache"))
+        .build()
+        .unwrap();
+
+    assert_eq!(config.metrics_port, 1234);
+    assert_eq!(config.cache_dir, PathBuf::from("/tmp/cache"));
+}
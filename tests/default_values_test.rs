@@ -1,4 +1,5 @@
 use service_builder::builder;
+use service_builder::error::BuildError;
 use std::time::Duration;
 
 #[builder]
@@ -31,7 +32,7 @@ fn test_build_with_defaults_all_defaults() {
     
     assert_eq!(config.default_ttl, Duration::from_secs(3600));
     assert_eq!(config.max_entries, 10_000);
-    assert_eq!(config.compression, false); // bool default
+    assert!(!config.compression); // bool default
     assert_eq!(config.custom_name, None);
     assert_eq!(config.connection_timeout, Duration::from_secs(5));
     assert_eq!(config.cache_dir, "/tmp/cache");
@@ -49,7 +50,7 @@ fn test_build_with_defaults_partial_override() {
     
     assert_eq!(config.default_ttl, Duration::from_secs(3600)); // uses default
     assert_eq!(config.max_entries, 50_000); // overridden
-    assert_eq!(config.compression, true); // overridden
+    assert!(config.compression); // overridden
     assert_eq!(config.custom_name, Some("my-cache".to_string())); // overridden
     assert_eq!(config.connection_timeout, Duration::from_secs(5)); // uses default
 }
@@ -61,8 +62,8 @@ fn test_strict_build_missing_required() {
         .build();
     
     assert!(result.is_err());
-    match result {
-        Err(service_builder::error::BuildError::MissingDependency(field)) => {
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => {
             assert_eq!(field, "cache_dir");
         }
         _ => panic!("Expected MissingDependency error"),
@@ -79,7 +80,7 @@ fn test_strict_build_with_defaults_provided() {
     // Defaults should still be applied for non-required fields
     assert_eq!(config.default_ttl, Duration::from_secs(3600));
     assert_eq!(config.max_entries, 10_000);
-    assert_eq!(config.compression, false);
+    assert!(!config.compression);
     assert_eq!(config.custom_name, None);
 }
 
@@ -101,7 +102,7 @@ fn test_all_fields_have_defaults() {
         .build_with_defaults()
         .unwrap();
     
-    assert_eq!(config.enabled, true);
+    assert!(config.enabled);
     assert_eq!(config.name, "default");
     assert_eq!(config.description, None);
 }
@@ -113,7 +114,7 @@ fn test_build_method_with_all_defaults() {
         .build()
         .unwrap();
     
-    assert_eq!(config.enabled, true);
+    assert!(config.enabled);
     assert_eq!(config.name, "default");
     assert_eq!(config.description, None);
 }
@@ -127,8 +128,8 @@ fn test_build_with_defaults_missing_required_field() {
         .build_with_defaults();
     
     assert!(result.is_err());
-    match result {
-        Err(service_builder::error::BuildError::MissingDependency(field)) => {
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => {
             assert_eq!(field, "cache_dir");
         }
         _ => panic!("Expected MissingDependency error"),
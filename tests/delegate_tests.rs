@@ -0,0 +1,121 @@
+use service_builder::{builder, delegatable};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[delegatable]
+trait UserRepository: Send + Sync {
+    fn find(&self, id: u32) -> Option<String>;
+    fn save(&self, id: u32, name: String);
+    fn count(&self) -> usize {
+        0
+    }
+}
+
+#[derive(Default)]
+struct MemoryRepository {
+    users: Mutex<HashMap<u32, String>>,
+}
+
+impl UserRepository for MemoryRepository {
+    fn find(&self, id: u32) -> Option<String> {
+        self.users.lock().unwrap().get(&id).cloned()
+    }
+
+    fn save(&self, id: u32, name: String) {
+        self.users.lock().unwrap().insert(id, name);
+    }
+
+    fn count(&self) -> usize {
+        self.users.lock().unwrap().len()
+    }
+}
+
+mod audit {
+    #[service_builder::delegatable]
+    pub trait AuditLog {
+        fn record(&mut self, entry: &str);
+        fn entries(&self) -> &[String];
+    }
+
+    #[derive(Default)]
+    pub struct VecLog(pub Vec<String>);
+
+    impl AuditLog for VecLog {
+        fn record(&mut self, entry: &str) {
+            self.0.push(entry.to_string());
+        }
+
+        fn entries(&self) -> &[String] {
+            &self.0
+        }
+    }
+}
+
+#[builder]
+struct CachedRepository {
+    #[builder(delegate(UserRepository, except(find = "Self::cached_find")))]
+    inner: Arc<dyn UserRepository>,
+    #[builder(default)]
+    cache: Mutex<HashMap<u32, String>>,
+    #[builder(default)]
+    misses: Mutex<u32>,
+}
+
+impl CachedRepository {
+    fn cached_find(&self, id: u32) -> Option<String> {
+        if let Some(name) = self.cache.lock().unwrap().get(&id) {
+            return Some(name.clone());
+        }
+        *self.misses.lock().unwrap() += 1;
+        let name = self.inner.find(id)?;
+        self.cache.lock().unwrap().insert(id, name.clone());
+        Some(name)
+    }
+}
+
+#[builder]
+struct AuditedService {
+    #[builder(delegate(audit::AuditLog))]
+    log: audit::VecLog,
+}
+
+fn cached_repository() -> CachedRepository {
+    CachedRepository::builder()
+        .inner(Arc::new(MemoryRepository::default()))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_methods_are_forwarded_to_the_field() {
+    let repository = cached_repository();
+
+    UserRepository::save(&repository, 1, "ada".to_string());
+
+    assert_eq!(repository.inner.find(1), Some("ada".to_string()));
+    assert_eq!(UserRepository::count(&repository), 1);
+}
+
+#[test]
+fn test_excepted_methods_call_the_given_function() {
+    let repository = cached_repository();
+    let users: &dyn UserRepository = &repository;
+    users.save(1, "ada".to_string());
+
+    assert_eq!(users.find(1), Some("ada".to_string()));
+    assert_eq!(users.find(1), Some("ada".to_string()));
+    assert_eq!(users.find(2), None);
+    assert_eq!(*repository.misses.lock().unwrap(), 2);
+}
+
+#[test]
+fn test_delegation_to_trait_in_another_module() {
+    use audit::AuditLog;
+
+    let mut service = AuditedService::builder().log(audit::VecLog::default()).build().unwrap();
+    service.record("login");
+    service.record("logout");
+
+    assert_eq!(service.entries(), &["login".to_string(), "logout".to_string()]);
+    assert_eq!(service.log.0.len(), 2);
+}
@@ -0,0 +1,110 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+struct Client {
+    base_url: String,
+    timeout: Duration,
+}
+
+fn make_client(timeout: &Duration, base_url: &str) -> Result<Client, String> {
+    if base_url.is_empty() {
+        return Err("base url must not be empty".to_string());
+    }
+    Ok(Client {
+        base_url: base_url.to_string(),
+        timeout: *timeout,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct Cache {
+    capacity: usize,
+}
+
+fn make_cache(capacity: &usize) -> Result<Cache, String> {
+    Ok(Cache { capacity: *capacity })
+}
+
+#[builder]
+struct ApiService {
+    // Declared before its inputs on purpose; build() resolves them first
+    #[builder(factory = "make_client(&timeout, &base_url)")]
+    client: Client,
+    base_url: String,
+    #[builder(default = "Duration::from_secs(5)")]
+    timeout: Duration,
+}
+
+#[builder]
+struct CachedService {
+    #[builder(default = "128")]
+    cache_capacity: usize,
+    #[builder(factory = "make_cache(&cache_capacity)", overridable)]
+    cache: Cache,
+    #[builder(factory = "Ok::<_, String>(cache.capacity * 2)")]
+    shards: usize,
+}
+
+#[test]
+fn test_factory_uses_other_fields() {
+    let service = ApiService::builder()
+        .base_url("https://api.example.com".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(service.client, Client {
+        base_url: "https://api.example.com".to_string(),
+        timeout: Duration::from_secs(5),
+    });
+    assert_eq!(service.base_url, "https://api.example.com");
+    assert_eq!(service.timeout, Duration::from_secs(5));
+}
+
+#[test]
+fn test_factory_error_is_initialization_error() {
+    let result = ApiService::builder()
+        .base_url(String::new())
+        .build_with_defaults();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::InitializationError(message)) => {
+            assert_eq!(message, "ApiService.client: base url must not be empty");
+        }
+        _ => panic!("Expected InitializationError"),
+    }
+}
+
+#[test]
+fn test_factory_inputs_still_required() {
+    let result = ApiService::builder().build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => assert_eq!(field, "base_url"),
+        _ => panic!("Expected MissingDependency error"),
+    }
+}
+
+#[test]
+fn test_chained_factories() {
+    let service = CachedService::builder()
+        .cache_capacity(16)
+        .build()
+        .unwrap();
+
+    assert_eq!(service.cache, Cache { capacity: 16 });
+    assert_eq!(service.shards, 32);
+}
+
+#[test]
+fn test_overridable_factory() {
+    let service = CachedService::builder()
+        .cache(Cache { capacity: 4 })
+        .build()
+        .unwrap();
+
+    assert_eq!(service.cache_capacity, 128);
+    assert_eq!(service.cache, Cache { capacity: 4 });
+    assert_eq!(service.shards, 8);
+}
@@ -16,6 +16,7 @@ fn test_getter() {
         .unwrap();
 
     assert_eq!(test.get_field1(), &"test".to_string());
+    assert_eq!(test.field2, 42);
     // Verify field2 doesn't have a getter
     // This should fail to compile if uncommented:
     // test.get_field2();
@@ -38,6 +39,7 @@ fn test_setter() {
 
     test.set_field1("new value".to_string());
     assert_eq!(test.field1, "new value".to_string());
+    assert_eq!(test.field2, 42);
     // Verify field2 doesn't have a setter
     // This should fail to compile if uncommented:
     // test.set_field2(100);
@@ -89,13 +91,14 @@ fn test_multiple_fields() {
         .string_field("test".to_string())
         .int_field(42)
         .bool_field(true)
-        .plain_field(3.14)
+        .plain_field(2.5)
         .build()
         .unwrap();
 
     // Test getters
     assert_eq!(test.get_string_field(), &"test".to_string());
     assert_eq!(test.get_bool_field(), &true);
+    assert_eq!(test.plain_field, 2.5);
 
     // Test setters
     test.set_int_field(100);
@@ -0,0 +1,71 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+
+// Globals are shared by every test in the process, so each test uses its own struct
+
+#[builder(global)]
+struct AppConfig {
+    name: String,
+}
+
+#[builder(global)]
+struct FeatureFlags {
+    #[builder(default)]
+    beta: bool,
+}
+
+#[builder(global)]
+struct Metrics {
+    prefix: String,
+}
+
+#[builder(global)]
+struct Clock {}
+
+#[test]
+fn test_init_global_and_access() {
+    AppConfig::init_global(AppConfig::builder().name("shop".to_string())).unwrap();
+
+    assert_eq!(AppConfig::global().name, "shop");
+    assert!(std::ptr::eq(AppConfig::global(), AppConfig::try_global().unwrap()));
+
+    let err = AppConfig::init_global(AppConfig::builder().name("other".to_string())).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid configuration: global AppConfig is already initialized");
+    assert_eq!(AppConfig::global().name, "shop");
+}
+
+#[test]
+fn test_failed_build_leaves_global_uninitialized() {
+    let result = Metrics::init_global(Metrics::builder());
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => assert_eq!(field, "prefix"),
+        _ => panic!("Expected MissingDependency error"),
+    }
+    assert!(Metrics::try_global().is_none());
+
+    Metrics::init_global(Metrics::builder().prefix("shop.".to_string())).unwrap();
+    assert_eq!(Metrics::global().prefix, "shop.");
+}
+
+#[test]
+#[should_panic(expected = "Clock::global() called before Clock::init_global()")]
+fn test_global_before_init_panics() {
+    let _ = Clock::global();
+}
+
+#[test]
+fn test_override_global_restores_previous_instance() {
+    FeatureFlags::init_global(FeatureFlags::builder()).unwrap();
+    let original = FeatureFlags::global();
+    assert!(!original.beta);
+
+    {
+        let _beta = FeatureFlags::override_global(FeatureFlags::builder().beta(true).build().unwrap());
+        assert!(FeatureFlags::global().beta);
+        // References taken before the override stay valid
+        assert!(!original.beta);
+    }
+
+    assert!(std::ptr::eq(FeatureFlags::global(), original));
+}
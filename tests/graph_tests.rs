@@ -0,0 +1,119 @@
+use service_builder::builder;
+use service_builder::graph::{Dependency, DependencyInfo, DependencyKind};
+use service_builder::registry::ServiceRegistry;
+use std::sync::{Arc, Weak};
+
+trait Cache: Send + Sync {}
+struct MemoryCache;
+impl Cache for MemoryCache {}
+
+#[builder]
+struct Config {
+    #[builder(default = "8080")]
+    port: u16,
+}
+
+#[builder]
+struct UserService {
+    #[builder(inject)]
+    cache: Arc<dyn Cache>,
+    #[builder(graph)]
+    config: Arc<Config>,
+    #[builder(optional)]
+    region: Option<String>,
+    #[builder(factory = "Ok::<_, String>(format!(\"users:{}\", config.port))")]
+    key_prefix: String,
+}
+
+#[builder]
+struct AppServices {
+    #[builder(graph)]
+    users: UserService,
+    #[builder(graph)]
+    config: Arc<Config>,
+    #[builder(weak)]
+    parent: Weak<Config>,
+    #[builder(skip)]
+    requests: u64,
+}
+
+fn app() -> AppServices {
+    let config = Arc::new(Config::builder().build_with_defaults().unwrap());
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn Cache>(Arc::new(MemoryCache));
+    let users = UserService::builder()
+        .config(config.clone())
+        .build_from(&registry)
+        .unwrap();
+    AppServices::builder().users(users).config(config).build().unwrap()
+}
+
+#[test]
+fn test_dependency_info_lists_fields_and_kinds() {
+    assert_eq!(UserService::dependency_info(), &[
+        Dependency { field: "cache", type_name: "Arc<dyn Cache>", kind: DependencyKind::Injected },
+        Dependency { field: "config", type_name: "Arc<Config>", kind: DependencyKind::Required },
+        Dependency { field: "region", type_name: "Option<String>", kind: DependencyKind::Optional },
+        Dependency { field: "key_prefix", type_name: "String", kind: DependencyKind::Factory },
+    ]);
+    assert_eq!(Config::dependency_info()[0].kind, DependencyKind::Defaulted);
+    let kinds: Vec<&str> = AppServices::dependency_info().iter().map(|d| d.kind.as_str()).collect();
+    assert_eq!(kinds, vec!["required", "required", "weak", "skipped"]);
+}
+
+#[test]
+fn test_graph_expands_marked_fields() {
+    let app = app();
+    let graph = app.graph();
+
+    assert_eq!(graph.service, "AppServices");
+    let users = graph.dependencies[0].graph.as_ref().unwrap();
+    assert_eq!(users.service, "UserService");
+    assert_eq!(users.dependencies[1].graph.as_ref().unwrap().service, "Config");
+    assert!(users.dependencies[0].graph.is_none());
+    assert!(graph.dependencies[2].graph.is_none());
+
+    assert_eq!(app.users.key_prefix, "users:8080");
+    assert_eq!(Arc::strong_count(&app.users.cache), 1);
+    assert!(app.users.region.is_none());
+    assert!(app.parent.upgrade().is_none());
+    assert_eq!(app.requests, 0);
+}
+
+#[test]
+fn test_graph_to_dot() {
+    assert_eq!(app().graph().to_dot(), concat!(
+        "digraph dependencies {\n",
+        "    \"AppServices\";\n",
+        "    \"AppServices\" -> \"UserService\" [label=\"users (required)\"];\n",
+        "    \"UserService.cache\" [label=\"Arc<dyn Cache>\"];\n",
+        "    \"UserService\" -> \"UserService.cache\" [label=\"cache (injected)\"];\n",
+        "    \"UserService\" -> \"Config\" [label=\"config (required)\"];\n",
+        "    \"Config.port\" [label=\"u16\"];\n",
+        "    \"Config\" -> \"Config.port\" [label=\"port (defaulted)\"];\n",
+        "    \"UserService.region\" [label=\"Option<String>\"];\n",
+        "    \"UserService\" -> \"UserService.region\" [label=\"region (optional)\"];\n",
+        "    \"UserService.key_prefix\" [label=\"String\"];\n",
+        "    \"UserService\" -> \"UserService.key_prefix\" [label=\"key_prefix (factory)\"];\n",
+        "    \"AppServices\" -> \"Config\" [label=\"config (required)\"];\n",
+        "    \"AppServices.parent\" [label=\"Weak<Config>\"];\n",
+        "    \"AppServices\" -> \"AppServices.parent\" [label=\"parent (weak)\"];\n",
+        "    \"AppServices.requests\" [label=\"u64\"];\n",
+        "    \"AppServices\" -> \"AppServices.requests\" [label=\"requests (skipped)\"];\n",
+        "}",
+    ));
+}
+
+#[test]
+fn test_graph_to_json() {
+    let config = Config::builder().build_with_defaults().unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(
+        config.graph().to_json(),
+        r#"{"service":"Config","dependencies":[{"field":"port","type":"u16","kind":"defaulted","graph":null}]}"#
+    );
+    assert!(app().graph().to_json().starts_with(
+        r#"{"service":"AppServices","dependencies":[{"field":"users","type":"UserService","kind":"required","graph":{"service":"UserService","#
+    ));
+}
@@ -0,0 +1,109 @@
+use service_builder::builder;
+use service_builder::health::{Health, HealthCheck, HealthStatus};
+use std::sync::Arc;
+
+struct Database {
+    connected: bool,
+}
+
+impl HealthCheck for Database {
+    fn check_health(&self) -> Health {
+        if self.connected {
+            Health::healthy()
+        } else {
+            Health::unhealthy("connection lost")
+        }
+    }
+}
+
+trait Cache: HealthCheck + Send + Sync {}
+
+struct SlowCache;
+
+impl HealthCheck for SlowCache {
+    fn check_health(&self) -> Health {
+        Health::degraded("p99 latency above \"250ms\"")
+    }
+}
+
+impl Cache for SlowCache {}
+
+#[builder(health)]
+struct UserService {
+    #[builder(health)]
+    database: Arc<Database>,
+    #[builder(health)]
+    cache: Arc<dyn Cache>,
+    #[builder(default = "3")]
+    retries: u32,
+}
+
+#[builder(health)]
+struct AppServices {
+    #[builder(health)]
+    user_service: UserService,
+    #[builder(health)]
+    audit_log: Database,
+}
+
+fn user_service(connected: bool) -> UserService {
+    UserService::builder()
+        .database(Arc::new(Database { connected }))
+        .cache(Arc::new(SlowCache))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_health_reports_each_field() {
+    let service = user_service(true);
+    let health = service.health();
+
+    assert_eq!(service.retries, 3);
+    assert_eq!(health.status, HealthStatus::Degraded);
+    assert!(health.is_ready());
+    let names: Vec<&str> = health.components.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["database", "cache"]);
+    assert_eq!(health.components[0].health, Health::healthy());
+    assert_eq!(health.components[1].health.status, HealthStatus::Degraded);
+}
+
+#[test]
+fn test_health_aggregates_nested_services() {
+    let app = AppServices::builder()
+        .user_service(user_service(false))
+        .audit_log(Database { connected: true })
+        .build()
+        .unwrap();
+
+    let health = app.check_health();
+
+    assert_eq!(health.status, HealthStatus::Unhealthy);
+    assert!(!health.is_ready());
+    let user_service = &health.components[0];
+    assert_eq!(user_service.name, "user_service");
+    assert_eq!(user_service.health.status, HealthStatus::Unhealthy);
+    assert_eq!(
+        user_service.health.components[0].health.message.as_deref(),
+        Some("connection lost")
+    );
+    assert_eq!(health.components[1].health.status, HealthStatus::Healthy);
+}
+
+#[test]
+fn test_health_report_json() {
+    let mut health = user_service(true).health();
+    for component in &mut health.components {
+        component.latency = std::time::Duration::from_micros(42);
+    }
+
+    assert_eq!(
+        health.to_json(),
+        concat!(
+            r#"{"status":"degraded","message":null,"components":["#,
+            r#"{"name":"database","latency_us":42,"status":"healthy","message":null,"components":[]},"#,
+            r#"{"name":"cache","latency_us":42,"status":"degraded","message":"p99 latency above \"250ms\"","components":[]}"#,
+            r#"]}"#,
+        )
+    );
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use service_builder::builder;
+use service_builder::error::BuildError;
+
+trait UserRepository {
+    fn initialize(&self) -> Result<(), String>;
+}
+
+struct MockRepository;
+impl UserRepository for MockRepository {
+    fn initialize(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+struct FailingRepository;
+impl UserRepository for FailingRepository {
+    fn initialize(&self) -> Result<(), String> {
+        Err("connection refused".to_string())
+    }
+}
+
+#[builder(init = "Self::init")]
+struct RepositoryService {
+    repository: Arc<dyn UserRepository>,
+    #[builder(default)]
+    initialized: bool,
+}
+
+impl RepositoryService {
+    fn init(&mut self) -> Result<(), String> {
+        self.repository.initialize()?;
+        self.initialized = true;
+        Ok(())
+    }
+}
+
+fn warm_cache(cache: &mut WarmCache) -> Result<(), std::fmt::Error> {
+    cache.entries.push("warm".to_string());
+    Ok(())
+}
+
+#[builder(init = "warm_cache")]
+struct WarmCache {
+    #[builder(default)]
+    entries: Vec<String>,
+}
+
+#[test]
+fn test_init_hook_runs_after_build() {
+    let service = RepositoryService::builder()
+        .repository(Arc::new(MockRepository))
+        .build()
+        .unwrap();
+
+    assert!(service.initialized);
+}
+
+#[test]
+fn test_init_hook_failure_is_initialization_error() {
+    let result = RepositoryService::builder()
+        .repository(Arc::new(FailingRepository))
+        .build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::InitializationError(message)) => {
+            assert_eq!(message, "RepositoryService: connection refused");
+        }
+        _ => panic!("Expected InitializationError"),
+    }
+}
+
+#[test]
+fn test_init_hook_runs_for_build_with_defaults() {
+    let cache = WarmCache::builder().build_with_defaults().unwrap();
+    assert_eq!(cache.entries, vec!["warm".to_string()]);
+}
+
+#[test]
+fn test_init_hook_not_run_when_dependency_missing() {
+    let result = RepositoryService::builder().build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => assert_eq!(field, "repository"),
+        _ => panic!("Expected MissingDependency error"),
+    }
+}
@@ -0,0 +1,132 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use service_builder::registry::ServiceRegistry;
+use std::sync::Arc;
+
+trait UserRepository: Send + Sync {
+    fn name(&self) -> &'static str;
+}
+
+trait Cache: Send + Sync {}
+trait Mailer: Send + Sync {}
+
+struct PrimaryUsers;
+impl UserRepository for PrimaryUsers {
+    fn name(&self) -> &'static str {
+        "primary"
+    }
+}
+
+struct ReplicaUsers;
+impl UserRepository for ReplicaUsers {
+    fn name(&self) -> &'static str {
+        "replica"
+    }
+}
+
+struct MemoryCache;
+impl Cache for MemoryCache {}
+
+#[builder]
+struct UserService {
+    #[builder(inject)]
+    repository: Arc<dyn UserRepository>,
+    #[builder(inject(name = "replica"))]
+    replica: Arc<dyn UserRepository>,
+    #[builder(inject)]
+    cache: Arc<dyn Cache>,
+    #[builder(default = "3")]
+    retries: u32,
+}
+
+#[builder]
+struct Notifier {
+    #[builder(inject)]
+    mailer: Arc<dyn Mailer>,
+    #[builder(inject)]
+    cache: Arc<dyn Cache>,
+    #[builder(inject(name = "audit"))]
+    audit: Arc<dyn UserRepository>,
+}
+
+fn registry() -> ServiceRegistry {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register::<dyn UserRepository>(Arc::new(PrimaryUsers))
+        .register_named::<dyn UserRepository>("replica", Arc::new(ReplicaUsers))
+        .register::<dyn Cache>(Arc::new(MemoryCache));
+    registry
+}
+
+#[test]
+fn test_build_from_resolves_injected_fields() {
+    let service = UserService::builder().build_from(&registry()).unwrap();
+
+    assert_eq!(service.repository.name(), "primary");
+    assert_eq!(service.replica.name(), "replica");
+    assert_eq!(service.retries, 3);
+    let _cache: &Arc<dyn Cache> = &service.cache;
+}
+
+#[test]
+fn test_explicitly_set_fields_are_kept() {
+    let service = UserService::builder()
+        .repository(Arc::new(ReplicaUsers))
+        .build_from(&registry())
+        .unwrap();
+
+    assert_eq!(service.repository.name(), "replica");
+}
+
+#[test]
+fn test_fill_from_then_build() {
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn UserRepository>(Arc::new(PrimaryUsers));
+
+    let service = UserService::builder()
+        .replica(Arc::new(ReplicaUsers))
+        .cache(Arc::new(MemoryCache))
+        .fill_from(&registry)
+        .unwrap()
+        .retries(5)
+        .build()
+        .unwrap();
+
+    assert_eq!(service.repository.name(), "primary");
+    assert_eq!(service.retries, 5);
+}
+
+#[test]
+fn test_all_unresolved_services_are_reported() {
+    let result = Notifier::builder()
+        .cache(Arc::new(MemoryCache))
+        .build_from(&ServiceRegistry::new());
+
+    let err = match result {
+        Err(err) => err,
+        Ok(_) => panic!("Expected build_from to fail"),
+    };
+    assert!(err.created_at().is_some());
+    match err.into_inner() {
+        BuildError::MissingDependency(services) => assert_eq!(
+            services,
+            "dyn inject_tests::Mailer, dyn inject_tests::UserRepository named \"audit\""
+        ),
+        other => panic!("Expected MissingDependency error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_notifier_resolves_when_everything_is_registered() {
+    let mut registry = registry();
+    struct Smtp;
+    impl Mailer for Smtp {}
+    registry
+        .register::<dyn Mailer>(Arc::new(Smtp))
+        .register_named::<dyn UserRepository>("audit", Arc::new(ReplicaUsers));
+
+    let notifier = Notifier::builder().build_from(&registry).unwrap();
+    assert_eq!(notifier.audit.name(), "replica");
+    let _mailer: &Arc<dyn Mailer> = &notifier.mailer;
+    let _cache: &Arc<dyn Cache> = &notifier.cache;
+}
@@ -0,0 +1,141 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct ReportRenderer {
+    templates: Vec<String>,
+}
+
+#[builder]
+struct ReportService {
+    name: String,
+    #[builder(lazy)]
+    renderer: ReportRenderer,
+    #[builder(lazy, default)]
+    cache_size: usize,
+    #[builder(lazy, setter)]
+    title: String,
+}
+
+fn counting_service(calls: &Arc<AtomicUsize>) -> ReportService {
+    let calls = calls.clone();
+    ReportService::builder()
+        .name("reports".to_string())
+        .renderer(move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            ReportRenderer { templates: vec!["monthly".to_string()] }
+        })
+        .title(|| "Reports".to_string())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_lazy_field_is_created_on_first_use() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let service = counting_service(&calls);
+
+    assert_eq!(service.name, "reports");
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(!service.renderer.is_initialized());
+
+    assert_eq!(service.get_renderer().templates, vec!["monthly".to_string()]);
+    assert_eq!(service.get_renderer().templates.len(), 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_lazy_field_is_created_once_across_threads() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let service = counting_service(&calls);
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| assert_eq!(service.get_renderer().templates.len(), 1));
+        }
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_lazy_default_and_setter() {
+    let mut service = counting_service(&Arc::new(AtomicUsize::new(0)));
+
+    assert_eq!(*service.get_cache_size(), 0);
+    assert_eq!(service.get_title(), "Reports");
+    service.set_title("Quarterly".to_string());
+    assert_eq!(service.get_title(), "Quarterly");
+}
+
+#[test]
+fn test_lazy_field_is_still_required() {
+    let result = ReportService::builder()
+        .name("reports".to_string())
+        .title(String::new)
+        .build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(field)) => assert_eq!(field, "renderer"),
+        _ => panic!("Expected MissingDependency error"),
+    }
+}
+
+#[test]
+fn test_failing_lazy_factory_is_initialization_error() {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    let service = ReportService::builder()
+        .name("reports".to_string())
+        .try_renderer(move || {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err("template directory missing")
+            } else {
+                Ok(ReportRenderer { templates: Vec::new() })
+            }
+        })
+        .title(String::new)
+        .build()
+        .unwrap();
+
+    match service.try_get_renderer() {
+        Err(BuildError::InitializationError(message)) => {
+            assert_eq!(message, "ReportService.renderer: template directory missing");
+        }
+        _ => panic!("Expected InitializationError"),
+    }
+
+    // A failed factory is retried on the next use
+    assert!(service.try_get_renderer().unwrap().templates.is_empty());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+#[should_panic(expected = "ReportService.renderer: template directory missing")]
+fn test_lazy_getter_panics_on_factory_failure() {
+    let service = ReportService::builder()
+        .name("reports".to_string())
+        .try_renderer(|| Err::<ReportRenderer, _>("template directory missing"))
+        .title(String::new)
+        .build()
+        .unwrap();
+
+    service.get_renderer();
+}
+
+// A struct named like a generated type parameter
+#[builder]
+struct E {
+    #[builder(lazy)]
+    value: u32,
+}
+
+#[test]
+fn test_lazy_on_struct_named_e() {
+    let eager = E::builder().value(|| 1).build().unwrap();
+    let fallible = E::builder().try_value(|| "2".parse::<u32>()).build().unwrap();
+
+    assert_eq!(*eager.get_value(), 1);
+    assert_eq!(*fallible.get_value(), 2);
+}
@@ -0,0 +1,149 @@
+use service_builder::builder;
+use service_builder::error::{BuildError, LifecyclePhase};
+use service_builder::lifecycle::{Lifecycle, LifecycleResult};
+use std::sync::{Arc, Mutex};
+
+type Events = Arc<Mutex<Vec<String>>>;
+
+struct Component {
+    name: &'static str,
+    events: Events,
+    fail_start: bool,
+    fail_stop: bool,
+}
+
+impl Component {
+    fn new(name: &'static str, events: &Events) -> Self {
+        Component { name, events: events.clone(), fail_start: false, fail_stop: false }
+    }
+}
+
+impl Lifecycle for Component {
+    fn start(&self) -> LifecycleResult {
+        if self.fail_start {
+            return Err(format!("{} refused to start", self.name).into());
+        }
+        self.events.lock().unwrap().push(format!("start {}", self.name));
+        Ok(())
+    }
+
+    fn stop(&self) -> LifecycleResult {
+        if self.fail_stop {
+            return Err(format!("{} refused to stop", self.name).into());
+        }
+        self.events.lock().unwrap().push(format!("stop {}", self.name));
+        Ok(())
+    }
+}
+
+trait Repository: Lifecycle + Send + Sync {}
+impl Repository for Component {}
+
+fn open_cache(_database: &Arc<dyn Repository>, events: &Events) -> Result<Arc<Component>, String> {
+    Ok(Arc::new(Component::new("cache", events)))
+}
+
+#[builder(lifecycle)]
+struct AppServices {
+    events: Events,
+    // Depends on `database`, so it starts after it despite being declared first
+    #[builder(lifecycle, factory = "open_cache(&database, &events)")]
+    cache: Arc<Component>,
+    #[builder(lifecycle)]
+    database: Arc<dyn Repository>,
+    #[builder(lifecycle)]
+    mailer: Component,
+}
+
+#[builder(lifecycle)]
+struct Platform {
+    #[builder(lifecycle)]
+    app: AppServices,
+    #[builder(lifecycle)]
+    metrics: Component,
+}
+
+fn app_services(events: &Events, mailer: Component) -> AppServices {
+    AppServices::builder()
+        .events(events.clone())
+        .database(Arc::new(Component::new("database", events)))
+        .mailer(mailer)
+        .build()
+        .unwrap()
+}
+
+fn recorded(events: &Events) -> Vec<String> {
+    events.lock().unwrap().clone()
+}
+
+#[test]
+fn test_start_in_dependency_order_and_stop_in_reverse() {
+    let events = Events::default();
+    let services = app_services(&events, Component::new("mailer", &events));
+
+    services.start_all().unwrap();
+    services.stop_all().unwrap();
+
+    assert_eq!(recorded(&services.events), recorded(&events));
+    assert_eq!(recorded(&events), vec![
+        "start database", "start cache", "start mailer",
+        "stop mailer", "stop cache", "stop database",
+    ]);
+}
+
+#[test]
+fn test_start_failure_rolls_back_started_components() {
+    let events = Events::default();
+    let mailer = Component { fail_start: true, ..Component::new("mailer", &events) };
+    let services = app_services(&events, mailer);
+
+    let err = services.start_all().unwrap_err();
+
+    assert_eq!(recorded(&events), vec![
+        "start database", "start cache", "stop cache", "stop database",
+    ]);
+    match err {
+        BuildError::LifecycleFailed(error) => {
+            assert_eq!(error.phase, LifecyclePhase::Start);
+            assert_eq!(error.failures.len(), 1);
+            assert_eq!(error.failures[0].component, "mailer");
+            assert_eq!(error.failures[0].error.to_string(), "mailer refused to start");
+        }
+        other => panic!("Expected LifecycleFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_stop_failures_are_aggregated() {
+    let events = Events::default();
+    let mailer = Component { fail_stop: true, ..Component::new("mailer", &events) };
+    let services = app_services(&events, mailer);
+    services.start_all().unwrap();
+
+    let err = services.stop_all().unwrap_err();
+
+    // The remaining components are still stopped
+    assert!(recorded(&events).ends_with(&["stop cache".to_string(), "stop database".to_string()]));
+    assert_eq!(
+        err.to_string(),
+        "Service lifecycle failed: stop failed: mailer (stop): mailer refused to stop"
+    );
+}
+
+#[test]
+fn test_nested_lifecycle_structs() {
+    let events = Events::default();
+    let platform = Platform::builder()
+        .app(app_services(&events, Component::new("mailer", &events)))
+        .metrics(Component::new("metrics", &events))
+        .build()
+        .unwrap();
+
+    platform.start().unwrap();
+    platform.stop().unwrap();
+
+    assert_eq!(recorded(&events), vec![
+        "start database", "start cache", "start mailer", "start metrics",
+        "stop metrics", "stop mailer", "stop cache", "stop database",
+    ]);
+}
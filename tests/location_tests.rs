@@ -0,0 +1,72 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+
+#[builder]
+struct LocatedService {
+    name: String,
+    cache: String,
+}
+
+#[test]
+fn test_build_error_records_call_sites() {
+    let builder = LocatedService::builder().name("svc".to_string());
+    let created_line = line!() - 1;
+    let result = builder.build();
+    let built_line = line!() - 1;
+
+    let err = match result {
+        Err(err) => err,
+        Ok(_) => panic!("Expected build to fail"),
+    };
+
+    let created_at = err.created_at().expect("creation site should be recorded");
+    let built_at = err.built_at().expect("build site should be recorded");
+    assert_eq!(created_at.file(), file!());
+    assert_eq!(created_at.line(), created_line);
+    assert_eq!(built_at.file(), file!());
+    assert_eq!(built_at.line(), built_line);
+
+    match err.kind() {
+        BuildError::MissingDependency(field) => assert_eq!(field, "cache"),
+        other => panic!("Expected MissingDependency error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_build_error_display_shows_location() {
+    let err = LocatedService::builder()
+        .cache("redis".to_string())
+        .build_with_defaults()
+        .err()
+        .unwrap();
+
+    let message = err.to_string();
+    assert!(message.starts_with("Missing required dependency: name (builder created at "));
+    assert!(message.contains(file!()));
+    assert!(format!("{:?}", err).contains(file!()));
+
+    // The underlying error is part of the message, so it is not repeated as the source
+    assert!(std::error::Error::source(&err).is_none());
+}
+
+#[test]
+fn test_into_inner_strips_location() {
+    let err = LocatedService::builder().build().err().unwrap();
+    assert!(err.created_at().is_some());
+
+    let inner = err.into_inner();
+    assert!(inner.created_at().is_none());
+    assert_eq!(inner.to_string(), "Missing required dependency: name");
+}
+
+#[test]
+fn test_successful_build_is_unaffected() {
+    let service = LocatedService::builder()
+        .name("svc".to_string())
+        .cache("redis".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(service.name, "svc");
+    assert_eq!(service.cache, "redis");
+}
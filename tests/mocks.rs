@@ -49,4 +49,13 @@ mod tests {
         let repo = Arc::new(MockRepository);
         assert!(repo.as_user_repository().is_some());
     }
+
+    #[test]
+    fn test_mock_cache_value() {
+        let cache = MockCache::builder()
+            .value("test".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(cache.value, "test");
+    }
 }
@@ -0,0 +1,107 @@
+use service_builder::builder;
+use service_builder::registry::ServiceRegistry;
+use std::sync::{Arc, Mutex};
+
+trait EventHandler: Send + Sync {
+    fn handle(&self, event: &str) -> String;
+}
+
+struct Named(&'static str);
+
+impl EventHandler for Named {
+    fn handle(&self, event: &str) -> String {
+        format!("{} handled {}", self.0, event)
+    }
+}
+
+trait Middleware: Send + Sync {
+    fn name(&self) -> &'static str;
+}
+
+impl Middleware for Named {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+#[builder]
+struct EventBus {
+    #[builder(inject_all)]
+    handlers: Vec<Arc<dyn EventHandler>>,
+    #[builder(inject_all)]
+    middleware: Vec<Arc<dyn Middleware>>,
+    #[builder(default)]
+    published: Mutex<Vec<String>>,
+}
+
+impl EventBus {
+    fn publish(&self, event: &str) -> Vec<String> {
+        self.published.lock().unwrap().push(event.to_string());
+        self.handlers.iter().map(|handler| handler.handle(event)).collect()
+    }
+
+    fn middleware_names(&self) -> Vec<&'static str> {
+        self.middleware.iter().map(|middleware| middleware.name()).collect()
+    }
+}
+
+#[test]
+fn test_inject_all_collects_in_registration_order() {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register_many::<dyn EventHandler>(Arc::new(Named("audit")))
+        .register_many::<dyn EventHandler>(Arc::new(Named("email")))
+        .register_many::<dyn EventHandler>(Arc::new(Named("metrics")));
+
+    let bus = EventBus::builder().build_from(&registry).unwrap();
+
+    assert_eq!(bus.publish("signup"), vec![
+        "audit handled signup", "email handled signup", "metrics handled signup",
+    ]);
+    assert_eq!(bus.published.lock().unwrap().len(), 1);
+    assert!(bus.middleware_names().is_empty());
+}
+
+#[test]
+fn test_inject_all_orders_by_priority() {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register_many::<dyn Middleware>(Arc::new(Named("logging")))
+        .register_many_with_priority::<dyn Middleware>(10, Arc::new(Named("auth")))
+        .register_many::<dyn Middleware>(Arc::new(Named("compression")))
+        .register_many_with_priority::<dyn Middleware>(-5, Arc::new(Named("fallback")));
+
+    let bus = EventBus::builder().build_from(&registry).unwrap();
+
+    assert_eq!(bus.middleware_names(), vec!["auth", "logging", "compression", "fallback"]);
+}
+
+#[test]
+fn test_explicit_handlers_are_kept() {
+    let mut registry = ServiceRegistry::new();
+    registry.register_many::<dyn EventHandler>(Arc::new(Named("audit")));
+
+    let bus = EventBus::builder()
+        .handlers(vec![Arc::new(Named("test"))])
+        .build_from(&registry)
+        .unwrap();
+
+    assert_eq!(bus.publish("signup"), vec!["test handled signup"]);
+}
+
+#[test]
+fn test_multi_bindings_are_separate_from_single_registrations() {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register::<dyn EventHandler>(Arc::new(Named("primary")))
+        .register_many::<dyn EventHandler>(Arc::new(Named("audit")))
+        .register_many::<dyn EventHandler>(Arc::new(Named("email")));
+
+    assert_eq!(registry.get::<dyn EventHandler>().unwrap().handle("x"), "primary handled x");
+    assert_eq!(registry.get_all::<dyn EventHandler>().len(), 2);
+    assert_eq!(registry.len(), 3);
+    assert_eq!(
+        format!("{:?}", registry),
+        "ServiceRegistry { services: [\"dyn multi_binding_tests::EventHandler\", \"dyn multi_binding_tests::EventHandler (2 bindings)\"] }"
+    );
+}
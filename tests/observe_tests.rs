@@ -0,0 +1,110 @@
+use service_builder::builder;
+use std::sync::{Arc, Mutex};
+
+#[builder]
+struct PoolConfig {
+    #[builder(observe, getter)]
+    max_connections: u32,
+    #[builder(observe, default = "Vec::new()")]
+    hosts: Vec<String>,
+    #[builder(getter, setter)]
+    name: String,
+}
+
+fn record<T: Clone + Send + 'static>(changes: &Arc<Mutex<Vec<(T, T)>>>) -> impl Fn(&T, &T) + Send + Sync + 'static {
+    let changes = Arc::clone(changes);
+    move |old: &T, new: &T| changes.lock().unwrap().push((old.clone(), new.clone()))
+}
+
+#[test]
+fn test_setter_notifies_listeners() {
+    let mut config = PoolConfig::builder()
+        .max_connections(10)
+        .name("primary".to_string())
+        .build_with_defaults()
+        .unwrap();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    config.subscribe_max_connections(record(&changes));
+
+    config.set_max_connections(20);
+    config.set_max_connections(5);
+
+    assert_eq!(*config.get_max_connections(), 5);
+    assert_eq!(*changes.lock().unwrap(), vec![(10, 20), (20, 5)]);
+}
+
+#[test]
+fn test_listeners_are_called_in_subscription_order() {
+    let mut config = PoolConfig::builder()
+        .max_connections(10)
+        .name("primary".to_string())
+        .build_with_defaults()
+        .unwrap();
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    for listener in ["resize", "reschedule"] {
+        let calls = Arc::clone(&calls);
+        config.subscribe_hosts(move |old: &Vec<String>, new: &Vec<String>| {
+            calls.lock().unwrap().push(format!("{}: {} -> {}", listener, old.len(), new.len()));
+        });
+    }
+
+    config.set_hosts(vec!["db-1".to_string(), "db-2".to_string()]);
+
+    assert_eq!(*calls.lock().unwrap(), vec!["resize: 0 -> 2", "reschedule: 0 -> 2"]);
+}
+
+#[test]
+fn test_unsubscribe_stops_notifications() {
+    let mut config = PoolConfig::builder()
+        .max_connections(10)
+        .name("primary".to_string())
+        .build_with_defaults()
+        .unwrap();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let subscription = config.subscribe_max_connections(record(&changes));
+    config.set_max_connections(20);
+
+    assert!(config.unsubscribe_max_connections(subscription));
+    assert!(!config.unsubscribe_max_connections(subscription));
+    config.set_max_connections(30);
+
+    assert_eq!(*changes.lock().unwrap(), vec![(10, 20)]);
+}
+
+#[test]
+fn test_unobserved_setter_does_not_notify() {
+    let mut config = PoolConfig::builder()
+        .max_connections(10)
+        .name("primary".to_string())
+        .build_with_defaults()
+        .unwrap();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    config.subscribe_max_connections(record(&changes));
+    config.set_name("replica".to_string());
+
+    assert!(changes.lock().unwrap().is_empty());
+    assert_eq!(config.get_name(), "replica");
+}
+
+// A struct named like a generated type parameter
+#[builder]
+struct F {
+    #[builder(observe)]
+    level: u8,
+}
+
+#[test]
+fn test_observe_on_struct_named_f() {
+    let mut f = F::builder().level(1).build().unwrap();
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    f.subscribe_level(record(&changes));
+
+    f.set_level(2);
+
+    assert_eq!(f.level, 2);
+    assert_eq!(*changes.lock().unwrap(), vec![(1, 2)]);
+}
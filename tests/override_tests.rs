@@ -0,0 +1,202 @@
+use service_builder::builder;
+use service_builder::container::{Container, Scope};
+use service_builder::error::BuildError;
+use service_builder::overrides::Overrides;
+use service_builder::registry::ServiceRegistry;
+use std::sync::{Arc, Mutex};
+
+trait PaymentGateway: Send + Sync {
+    fn charge(&self, cents: u32) -> Result<String, String>;
+}
+
+struct StripeGateway;
+
+impl PaymentGateway for StripeGateway {
+    fn charge(&self, cents: u32) -> Result<String, String> {
+        Ok(format!("stripe charged {}", cents))
+    }
+}
+
+#[derive(Default)]
+struct MockGateway {
+    charges: Mutex<Vec<u32>>,
+}
+
+impl PaymentGateway for MockGateway {
+    fn charge(&self, cents: u32) -> Result<String, String> {
+        self.charges.lock().unwrap().push(cents);
+        Ok(format!("mock charged {}", cents))
+    }
+}
+
+trait Mailer: Send + Sync {}
+struct SmtpMailer;
+impl Mailer for SmtpMailer {}
+
+fn connect_gateway() -> Result<Arc<dyn PaymentGateway>, String> {
+    Ok(Arc::new(StripeGateway))
+}
+
+#[builder]
+struct CheckoutService {
+    #[builder(factory = "connect_gateway()")]
+    gateway: Arc<dyn PaymentGateway>,
+    #[builder(default = "30")]
+    timeout_secs: u32,
+}
+
+impl CheckoutService {
+    fn checkout(&self, cents: u32) -> String {
+        self.gateway.charge(cents).unwrap()
+    }
+}
+
+#[builder]
+struct Storefront {
+    gateway: Arc<dyn PaymentGateway>,
+}
+
+#[builder]
+struct PaymentRouter {
+    #[builder(inject(qualifier = "primary"))]
+    primary: Arc<dyn PaymentGateway>,
+    #[builder(inject(qualifier = "backup"))]
+    backup: Arc<dyn PaymentGateway>,
+}
+
+#[test]
+fn test_override_replaces_factory_value() {
+    let mock = Arc::new(MockGateway::default());
+    let overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(mock.clone())
+        .install();
+
+    let service = CheckoutService::builder().build().unwrap();
+
+    assert_eq!(service.checkout(1200), "mock charged 1200");
+    assert_eq!(*mock.charges.lock().unwrap(), vec![1200]);
+    assert_eq!(service.timeout_secs, 30);
+    overrides.verify().unwrap();
+}
+
+#[test]
+fn test_override_takes_precedence_over_configured_value() {
+    let _overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .install();
+
+    let storefront = Storefront::builder()
+        .gateway(Arc::new(StripeGateway))
+        .build()
+        .unwrap();
+
+    assert_eq!(storefront.gateway.charge(5).unwrap(), "mock charged 5");
+}
+
+#[test]
+fn test_overrides_are_removed_with_the_guard() {
+    {
+        let _overrides = Overrides::new()
+            .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+            .install();
+        assert_eq!(CheckoutService::builder().build().unwrap().checkout(1), "mock charged 1");
+    }
+
+    assert_eq!(CheckoutService::builder().build().unwrap().checkout(1), "stripe charged 1");
+}
+
+#[test]
+fn test_registry_and_container_lookups_use_overrides() {
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn PaymentGateway>(Arc::new(StripeGateway));
+    let mut container = Container::new();
+    container.register::<dyn PaymentGateway, _>(Scope::Singleton, |_| Ok(Arc::new(StripeGateway)));
+
+    let _overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .install();
+
+    assert_eq!(registry.get::<dyn PaymentGateway>().unwrap().charge(1).unwrap(), "mock charged 1");
+    assert_eq!(container.resolve::<dyn PaymentGateway>().unwrap().charge(2).unwrap(), "mock charged 2");
+}
+
+#[test]
+fn test_named_overrides_replace_only_their_name() {
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn PaymentGateway>(Arc::new(StripeGateway));
+    registry.register_named::<dyn PaymentGateway>("backup", Arc::new(StripeGateway));
+    registry.register_named::<dyn PaymentGateway>("primary", Arc::new(StripeGateway));
+
+    let _overrides = Overrides::new()
+        .override_named::<dyn PaymentGateway>("backup", Arc::new(MockGateway::default()))
+        .install();
+
+    assert_eq!(registry.get_named::<dyn PaymentGateway>("backup").unwrap().charge(1).unwrap(), "mock charged 1");
+    assert_eq!(registry.get_named::<dyn PaymentGateway>("primary").unwrap().charge(2).unwrap(), "stripe charged 2");
+    assert_eq!(registry.get::<dyn PaymentGateway>().unwrap().charge(3).unwrap(), "stripe charged 3");
+
+    let payments = PaymentRouter::builder().build_from(&registry).unwrap();
+    assert_eq!(payments.backup.charge(4).unwrap(), "mock charged 4");
+    assert_eq!(payments.primary.charge(5).unwrap(), "stripe charged 5");
+}
+
+#[test]
+fn test_unnamed_override_leaves_named_and_multi_bindings() {
+    let mut registry = ServiceRegistry::new();
+    registry.register_named::<dyn PaymentGateway>("primary", Arc::new(StripeGateway));
+    registry.register_many::<dyn PaymentGateway>(Arc::new(StripeGateway));
+    registry.register_many::<dyn PaymentGateway>(Arc::new(StripeGateway));
+
+    let overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .install();
+
+    assert_eq!(registry.get_named::<dyn PaymentGateway>("primary").unwrap().charge(1).unwrap(), "stripe charged 1");
+    let all = registry.get_all::<dyn PaymentGateway>();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].charge(2).unwrap(), "stripe charged 2");
+
+    assert_eq!(registry.get::<dyn PaymentGateway>().unwrap().charge(3).unwrap(), "mock charged 3");
+    overrides.verify().unwrap();
+}
+
+#[test]
+fn test_verify_reports_unused_overrides() {
+    let overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .override_with::<dyn Mailer>(Arc::new(SmtpMailer))
+        .override_named::<dyn PaymentGateway>("backup", Arc::new(MockGateway::default()))
+        .install();
+
+    assert_eq!(CheckoutService::builder().build().unwrap().checkout(1), "mock charged 1");
+
+    match overrides.verify() {
+        Err(BuildError::ConfigurationError(message)) => {
+            assert_eq!(
+                message,
+                "unused overrides: dyn override_tests::Mailer, dyn override_tests::PaymentGateway named \"backup\""
+            );
+        }
+        _ => panic!("Expected ConfigurationError"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "unused overrides: dyn override_tests::PaymentGateway")]
+fn test_dropping_guard_with_unused_override_panics() {
+    let _overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .install();
+}
+
+#[test]
+fn test_overrides_are_per_thread() {
+    let _overrides = Overrides::new()
+        .override_with::<dyn PaymentGateway>(Arc::new(MockGateway::default()))
+        .install();
+
+    let other_thread = std::thread::spawn(|| CheckoutService::builder().build().unwrap().checkout(3));
+
+    assert_eq!(other_thread.join().unwrap(), "stripe charged 3");
+    assert_eq!(CheckoutService::builder().build().unwrap().checkout(3), "mock charged 3");
+}
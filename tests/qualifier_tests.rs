@@ -0,0 +1,95 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use service_builder::registry::ServiceRegistry;
+use std::sync::Arc;
+
+trait Cache: Send + Sync {
+    fn kind(&self) -> &'static str;
+}
+
+struct LocalCache;
+impl Cache for LocalCache {
+    fn kind(&self) -> &'static str {
+        "local"
+    }
+}
+
+struct RedisCache;
+impl Cache for RedisCache {
+    fn kind(&self) -> &'static str {
+        "redis"
+    }
+}
+
+#[builder]
+struct SessionService {
+    #[builder(inject(qualifier = "local"))]
+    cache: Arc<dyn Cache>,
+}
+
+#[builder]
+struct CatalogService {
+    #[builder(inject(qualifier = "distributed"))]
+    cache: Arc<dyn Cache>,
+    #[builder(inject(qualifier = "local"))]
+    hot_items: Arc<dyn Cache>,
+}
+
+#[builder]
+struct SearchService {
+    #[builder(inject(qualifier = "search"))]
+    cache: Arc<dyn Cache>,
+}
+
+fn registry() -> ServiceRegistry {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register_named::<dyn Cache>("local", Arc::new(LocalCache))
+        .register_named::<dyn Cache>("distributed", Arc::new(RedisCache));
+    registry
+}
+
+#[test]
+fn test_qualified_fields_get_their_own_implementation() {
+    let registry = registry();
+
+    let sessions = SessionService::builder().build_from(&registry).unwrap();
+    let catalog = CatalogService::builder().build_from(&registry).unwrap();
+
+    assert_eq!(sessions.cache.kind(), "local");
+    assert_eq!(catalog.cache.kind(), "redis");
+    assert!(Arc::ptr_eq(&sessions.cache, &catalog.hot_items));
+}
+
+#[test]
+fn test_qualifiers_are_listed() {
+    let registry = registry();
+
+    assert_eq!(registry.qualifiers::<dyn Cache>(), vec!["distributed", "local"]);
+    assert!(registry.qualifiers::<LocalCache>().is_empty());
+}
+
+#[test]
+fn test_unknown_qualifier_lists_available_ones() {
+    let result = SearchService::builder().build_from(&registry());
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::MissingDependency(service)) => assert_eq!(
+            service,
+            "dyn qualifier_tests::Cache named \"search\" (available qualifiers: \"distributed\", \"local\")"
+        ),
+        Err(other) => panic!("Expected MissingDependency error, got {:?}", other),
+        Ok(search) => panic!("Resolved a {} cache", search.cache.kind()),
+    }
+}
+
+#[test]
+fn test_unqualified_lookup_lists_available_qualifiers() {
+    match registry().get::<dyn Cache>() {
+        Err(BuildError::MissingDependency(service)) => assert_eq!(
+            service,
+            "dyn qualifier_tests::Cache (available qualifiers: \"distributed\", \"local\")"
+        ),
+        _ => panic!("Expected MissingDependency error"),
+    }
+}
@@ -0,0 +1,94 @@
+use service_builder::error::BuildError;
+use service_builder::registry::ServiceRegistry;
+use std::sync::Arc;
+
+trait UserRepository: Send + Sync {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+struct InMemoryUsers;
+
+impl UserRepository for InMemoryUsers {
+    fn find(&self, id: u32) -> Option<String> {
+        (id == 1).then(|| "alice".to_string())
+    }
+}
+
+struct ReplicaUsers;
+
+impl UserRepository for ReplicaUsers {
+    fn find(&self, _id: u32) -> Option<String> {
+        Some("replica".to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Settings {
+    region: String,
+}
+
+#[test]
+fn test_register_and_get_trait_object() {
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn UserRepository>(Arc::new(InMemoryUsers));
+
+    let users = registry.get::<dyn UserRepository>().unwrap();
+    assert_eq!(users.find(1), Some("alice".to_string()));
+    assert!(registry.contains::<dyn UserRepository>());
+}
+
+#[test]
+fn test_register_concrete_type() {
+    let mut registry = ServiceRegistry::new();
+    let settings = Arc::new(Settings { region: "eu".to_string() });
+    registry.register(settings.clone());
+
+    let resolved = registry.get::<Settings>().unwrap();
+    assert!(Arc::ptr_eq(&settings, &resolved));
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn test_named_registrations_are_separate() {
+    let mut registry = ServiceRegistry::new();
+    registry
+        .register::<dyn UserRepository>(Arc::new(InMemoryUsers))
+        .register_named::<dyn UserRepository>("replica", Arc::new(ReplicaUsers));
+
+    let primary = registry.get::<dyn UserRepository>().unwrap();
+    let replica = registry.get_named::<dyn UserRepository>("replica").unwrap();
+    assert_eq!(primary.find(2), None);
+    assert_eq!(replica.find(2), Some("replica".to_string()));
+    assert!(registry.contains_named::<dyn UserRepository>("replica"));
+    assert!(!registry.contains_named::<dyn UserRepository>("archive"));
+}
+
+#[test]
+fn test_missing_service_reports_type_name() {
+    let registry = ServiceRegistry::new();
+
+    match registry.get::<dyn UserRepository>() {
+        Err(BuildError::MissingDependency(name)) => {
+            assert_eq!(name, "dyn registry_tests::UserRepository");
+        }
+        _ => panic!("Expected MissingDependency error"),
+    }
+    match registry.get_named::<Settings>("primary") {
+        Err(BuildError::MissingDependency(name)) => {
+            assert_eq!(name, "registry_tests::Settings named \"primary\"");
+        }
+        _ => panic!("Expected MissingDependency error"),
+    }
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_registering_again_replaces_service() {
+    let mut registry = ServiceRegistry::new();
+    registry.register::<dyn UserRepository>(Arc::new(InMemoryUsers));
+    registry.register::<dyn UserRepository>(Arc::new(ReplicaUsers));
+
+    let users = registry.get::<dyn UserRepository>().unwrap();
+    assert_eq!(users.find(1), Some("replica".to_string()));
+    assert_eq!(format!("{:?}", registry), "ServiceRegistry { services: [\"dyn registry_tests::UserRepository\"] }");
+}
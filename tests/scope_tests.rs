@@ -0,0 +1,154 @@
+use service_builder::builder;
+use service_builder::container::{Container, Scope, Scoped};
+use service_builder::error::BuildError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[builder(scope = "singleton")]
+struct Database {
+    url: String,
+}
+
+#[builder(scope = "scoped")]
+struct UnitOfWork {
+    database: Arc<Database>,
+    changes: Mutex<Vec<String>>,
+}
+
+#[builder(scope = "transient")]
+struct RequestId {
+    value: usize,
+}
+
+#[builder(scope = "transient")]
+struct UserHandler {
+    unit_of_work: Arc<UnitOfWork>,
+    request_id: Arc<RequestId>,
+}
+
+#[builder(scope = "singleton")]
+struct ReportCache {
+    unit_of_work: Arc<UnitOfWork>,
+}
+
+fn container() -> Container {
+    let next_id = Arc::new(AtomicUsize::new(0));
+    let mut container = Container::new();
+    container
+        .register_service(|_| Database::builder().url("postgres://localhost".to_string()).build())
+        .register_service(|r| {
+            UnitOfWork::builder()
+                .database(r.resolve()?)
+                .changes(Mutex::new(Vec::new()))
+                .build()
+        })
+        .register_service(move |_| {
+            RequestId::builder()
+                .value(next_id.fetch_add(1, Ordering::SeqCst))
+                .build()
+        })
+        .register_service(|r| {
+            UserHandler::builder()
+                .unit_of_work(r.resolve()?)
+                .request_id(r.resolve()?)
+                .build()
+        });
+    container
+}
+
+#[test]
+fn test_scope_attribute() {
+    assert_eq!(Database::SCOPE, Scope::Singleton);
+    assert_eq!(UnitOfWork::SCOPE, Scope::Scoped);
+    assert_eq!(RequestId::SCOPE, Scope::Transient);
+}
+
+#[test]
+fn test_singletons_are_shared_across_scopes() {
+    let container = container();
+    let first = container.create_scope();
+    let second = container.create_scope();
+
+    let database = container.resolve::<Database>().unwrap();
+    assert_eq!(database.url, "postgres://localhost");
+    assert!(Arc::ptr_eq(&database, &first.resolve::<Database>().unwrap()));
+    assert!(Arc::ptr_eq(&database, &second.resolve::<Database>().unwrap()));
+}
+
+#[test]
+fn test_transients_are_built_every_time() {
+    let container = container();
+
+    let first = container.resolve::<RequestId>().unwrap();
+    let second = container.resolve::<RequestId>().unwrap();
+
+    assert_ne!(first.value, second.value);
+}
+
+#[test]
+fn test_scoped_instances_are_cached_per_scope() {
+    let container = container();
+    let request = container.create_scope();
+
+    let handler = request.resolve::<UserHandler>().unwrap();
+    let other_handler = request.resolve::<UserHandler>().unwrap();
+    handler.unit_of_work.changes.lock().unwrap().push("insert user".to_string());
+
+    // Both handlers share the request's unit of work but get their own request id
+    assert!(Arc::ptr_eq(&handler.unit_of_work, &other_handler.unit_of_work));
+    assert_ne!(handler.request_id.value, other_handler.request_id.value);
+    assert_eq!(other_handler.unit_of_work.changes.lock().unwrap().len(), 1);
+
+    let next_request = container.create_scope();
+    let unit_of_work = next_request.resolve::<UnitOfWork>().unwrap();
+    assert!(!Arc::ptr_eq(&handler.unit_of_work, &unit_of_work));
+    assert!(Arc::ptr_eq(&handler.unit_of_work.database, &unit_of_work.database));
+}
+
+#[test]
+fn test_scoped_instances_are_dropped_with_their_scope() {
+    let container = container();
+    let request = container.create_scope();
+    let unit_of_work = Arc::downgrade(&request.resolve::<UnitOfWork>().unwrap());
+
+    assert!(unit_of_work.upgrade().is_some());
+    drop(request);
+    assert!(unit_of_work.upgrade().is_none());
+}
+
+#[test]
+fn test_scoped_service_needs_a_scope() {
+    let container = container();
+
+    match container.resolve::<UnitOfWork>() {
+        Err(BuildError::ScopeViolation(message)) => {
+            assert_eq!(message, "scoped scope_tests::UnitOfWork resolved outside of a scope");
+        }
+        _ => panic!("Expected ScopeViolation error"),
+    }
+}
+
+#[test]
+fn test_singleton_cannot_capture_scoped_dependency() {
+    let mut container = container();
+    container.register_service(|r| ReportCache::builder().unit_of_work(r.resolve()?).build());
+    let request = container.create_scope();
+
+    match request.resolve::<ReportCache>() {
+        Err(BuildError::ScopeViolation(message)) => {
+            assert_eq!(message, "singleton scope_tests::ReportCache depends on scoped scope_tests::UnitOfWork");
+        }
+        Err(other) => panic!("Expected ScopeViolation, got {:?}", other),
+        Ok(cache) => panic!("Singleton captured {:?}", cache.unit_of_work.changes),
+    }
+}
+
+#[test]
+fn test_unregistered_service_is_missing() {
+    let container = Container::new();
+
+    match container.resolve::<Database>() {
+        Err(BuildError::MissingDependency(name)) => assert_eq!(name, "scope_tests::Database"),
+        _ => panic!("Expected MissingDependency error"),
+    }
+}
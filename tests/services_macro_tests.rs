@@ -0,0 +1,135 @@
+use service_builder::error::BuildError;
+use service_builder::{builder, services};
+use std::sync::Arc;
+
+trait UserRepository: Send + Sync {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+struct InMemoryUsers {
+    prefix: String,
+}
+
+impl UserRepository for InMemoryUsers {
+    fn find(&self, id: u32) -> Option<String> {
+        Some(format!("{}{}", self.prefix, id))
+    }
+}
+
+#[builder]
+struct Config {
+    #[builder(default = "\"user-\".to_string()")]
+    prefix: String,
+}
+
+#[builder]
+struct UserService {
+    repository: Arc<dyn UserRepository>,
+    config: Arc<Config>,
+}
+
+#[builder]
+struct PostService {
+    users: Arc<UserService>,
+}
+
+services! {
+    /// Every service of the test application.
+    struct AppServices {
+        // Declared before their dependencies on purpose
+        posts: Arc<PostService> = Arc::new(PostService::builder().users(users.clone()).build()?),
+        users: Arc<UserService> = Arc::new(UserService::builder()
+            .repository(repo.clone())
+            .config(config.clone())
+            .build()?),
+        repo: Arc<dyn UserRepository> = Arc::new(InMemoryUsers { prefix: config.prefix.clone() }),
+        config: Arc<Config> = Arc::new(Config::builder().build()?),
+    }
+}
+
+services! {
+    struct AutoServices {
+        service: UserService = auto(repository: repo, config),
+        repo: Arc<dyn UserRepository> = Arc::new(InMemoryUsers { prefix: "auto-".to_string() }),
+        config: Arc<Config> = Arc::new(Config::builder().prefix("ignored".to_string()).build()?),
+        defaults: Config = auto,
+    }
+}
+
+services! {
+    struct InferredServices {
+        // Built from `Arc<dyn UserRepository>` and `Arc<Config>`, found by type
+        users: Arc<UserService> = Arc::new(UserService::builder().repository(repo.clone()).config(config.clone()).build()?),
+        posts: PostService = auto,
+        service: UserService = auto,
+        repo: Arc<dyn UserRepository> = Arc::new(InMemoryUsers { prefix: "inferred-".to_string() }),
+        config: Arc<Config> = Arc::new(Config::builder().build()?),
+    }
+}
+
+services! {
+    struct OverriddenServices {
+        // Two services provide `Arc<Config>`, so the field to use is named
+        service: UserService = auto(config: primary),
+        repo: Arc<dyn UserRepository> = Arc::new(InMemoryUsers { prefix: "overridden-".to_string() }),
+        primary: Arc<Config> = Arc::new(Config::builder().prefix("primary-".to_string()).build()?),
+        fallback: Arc<Config> = Arc::new(Config::builder().prefix("fallback-".to_string()).build()?),
+    }
+}
+
+fn failing_config() -> Result<Config, BuildError> {
+    Err(BuildError::ConfigurationError("config file missing".to_string()))
+}
+
+services! {
+    struct FailingServices {
+        config: Config = failing_config()?,
+    }
+}
+
+#[test]
+fn test_services_are_built_in_dependency_order() {
+    let services = AppServices::build().unwrap();
+
+    assert_eq!(services.repo.find(7), Some("user-7".to_string()));
+    assert!(Arc::ptr_eq(&services.posts.users, &services.users));
+    assert!(Arc::ptr_eq(&services.users.config, &services.config));
+    assert_eq!(services.posts.users.repository.find(1), Some("user-1".to_string()));
+}
+
+#[test]
+fn test_auto_providers_use_builder_fields() {
+    let services = AutoServices::build().unwrap();
+
+    assert!(Arc::ptr_eq(&services.service.repository, &services.repo));
+    assert!(Arc::ptr_eq(&services.service.config, &services.config));
+    assert_eq!(services.service.config.prefix, "ignored");
+    assert_eq!(services.defaults.prefix, "user-");
+}
+
+#[test]
+fn test_auto_infers_dependencies_from_field_types() {
+    let services = InferredServices::build().unwrap();
+
+    assert!(Arc::ptr_eq(&services.posts.users, &services.users));
+    assert!(Arc::ptr_eq(&services.service.repository, &services.repo));
+    assert!(Arc::ptr_eq(&services.service.config, &services.config));
+    assert_eq!(services.service.repository.find(3), Some("inferred-3".to_string()));
+}
+
+#[test]
+fn test_auto_arguments_choose_between_services_of_one_type() {
+    let services = OverriddenServices::build().unwrap();
+
+    assert!(Arc::ptr_eq(&services.service.config, &services.primary));
+    assert!(Arc::ptr_eq(&services.service.repository, &services.repo));
+    assert_eq!(services.fallback.prefix, "fallback-");
+}
+
+#[test]
+fn test_provider_errors_are_returned() {
+    match FailingServices::build().map(|services| services.config.prefix) {
+        Err(BuildError::ConfigurationError(message)) => assert_eq!(message, "config file missing"),
+        _ => panic!("Expected ConfigurationError"),
+    }
+}
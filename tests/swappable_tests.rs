@@ -0,0 +1,94 @@
+use service_builder::builder;
+use std::sync::Arc;
+use std::thread;
+
+trait Credentials: Send + Sync {
+    fn token(&self) -> String;
+}
+
+struct StaticToken(&'static str);
+
+impl Credentials for StaticToken {
+    fn token(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+fn initial_token() -> Result<Arc<dyn Credentials>, String> {
+    Ok(Arc::new(StaticToken("token-1")))
+}
+
+#[builder]
+struct ApiClient {
+    #[builder(swappable)]
+    credentials: Arc<dyn Credentials>,
+    #[builder(swappable, setter)]
+    endpoint: Arc<String>,
+    #[builder(factory = "Ok::<_, String>(format!(\"Bearer {}\", credentials.token()))")]
+    header: String,
+}
+
+#[builder]
+struct Gateway {
+    #[builder(swappable, factory = "initial_token()")]
+    credentials: Arc<dyn Credentials>,
+}
+
+#[test]
+fn test_swap_replaces_the_service() {
+    let client = ApiClient::builder()
+        .credentials(Arc::new(StaticToken("token-1")))
+        .endpoint(Arc::new("https://api.example.com".to_string()))
+        .build()
+        .unwrap();
+    assert_eq!(client.load_credentials().token(), "token-1");
+    assert_eq!(client.header, "Bearer token-1");
+
+    let previous = client.swap_credentials(Arc::new(StaticToken("token-2")));
+    assert_eq!(previous.token(), "token-1");
+    assert_eq!(client.load_credentials().token(), "token-2");
+}
+
+#[test]
+fn test_loaded_service_outlives_swap() {
+    let client = ApiClient::builder()
+        .credentials(Arc::new(StaticToken("token-1")))
+        .endpoint(Arc::new("https://api.example.com".to_string()))
+        .build()
+        .unwrap();
+
+    let in_flight = client.load_credentials();
+    client.swap_credentials(Arc::new(StaticToken("token-2")));
+    assert_eq!(in_flight.token(), "token-1");
+    assert_eq!(client.load_credentials().token(), "token-2");
+}
+
+#[test]
+fn test_swap_through_shared_service() {
+    let gateway = Arc::new(Gateway::builder().build().unwrap());
+    assert_eq!(gateway.load_credentials().token(), "token-1");
+
+    let rotator = Arc::clone(&gateway);
+    thread::spawn(move || {
+        rotator.swap_credentials(Arc::new(StaticToken("rotated")));
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(gateway.load_credentials().token(), "rotated");
+}
+
+#[test]
+fn test_setter_on_swappable_field() {
+    let mut client = ApiClient::builder()
+        .credentials(Arc::new(StaticToken("token-1")))
+        .endpoint(Arc::new("https://old.example.com".to_string()))
+        .build()
+        .unwrap();
+
+    client.set_endpoint(Arc::new("https://new.example.com".to_string()));
+    assert_eq!(*client.load_endpoint(), "https://new.example.com");
+
+    client.swap_endpoint(Arc::new("https://other.example.com".to_string()));
+    assert_eq!(*client.load_endpoint(), "https://other.example.com");
+}
@@ -0,0 +1,11 @@
+use service_builder::builder;
+
+#[builder]
+struct Ports {
+    #[builder(default = "b + 1")]
+    a: u16,
+    #[builder(default = "a + 1")]
+    b: u16,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use service_builder::{builder, delegatable};
+
+#[delegatable]
+trait UserRepository {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+struct MemoryRepository;
+
+impl UserRepository for MemoryRepository {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// `lookup` is not a method of the trait
+#[builder]
+struct CachedRepository {
+    #[builder(delegate(UserRepository, except(lookup = "Self::lookup")))]
+    inner: MemoryRepository,
+}
+
+impl CachedRepository {
+    fn lookup(&self, id: u32) -> Option<String> {
+        self.inner.find(id)
+    }
+}
+
+fn main() {}
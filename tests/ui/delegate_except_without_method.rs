@@ -0,0 +1,23 @@
+use service_builder::{builder, delegatable};
+
+#[delegatable]
+trait UserRepository {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+struct MemoryRepository;
+
+impl UserRepository for MemoryRepository {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// `find` calls `Self::cached_find`, which the struct does not have
+#[builder]
+struct CachedRepository {
+    #[builder(delegate(UserRepository, except(find = "Self::cached_find")))]
+    inner: MemoryRepository,
+}
+
+fn main() {}
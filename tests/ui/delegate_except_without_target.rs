@@ -0,0 +1,29 @@
+use service_builder::{builder, delegatable};
+
+#[delegatable]
+trait UserRepository {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+struct MemoryRepository;
+
+impl UserRepository for MemoryRepository {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// `find` is excepted without naming the function to call instead
+#[builder]
+struct CachedRepository {
+    #[builder(delegate(UserRepository, except(find)))]
+    inner: MemoryRepository,
+}
+
+impl CachedRepository {
+    fn find(&self, id: u32) -> Option<String> {
+        self.inner.find(id)
+    }
+}
+
+fn main() {}
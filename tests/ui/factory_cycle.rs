@@ -0,0 +1,11 @@
+use service_builder::builder;
+
+#[builder]
+struct Client {
+    #[builder(factory = "Ok::<_, String>(b.clone())")]
+    a: String,
+    #[builder(factory = "Ok::<_, String>(a.clone())")]
+    b: String,
+}
+
+fn main() {}
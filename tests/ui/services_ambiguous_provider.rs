@@ -0,0 +1,23 @@
+use service_builder::{builder, services};
+use std::sync::Arc;
+
+#[builder]
+struct Config {
+    #[builder(default)]
+    prefix: String,
+}
+
+#[builder]
+struct UserService {
+    config: Arc<Config>,
+}
+
+services! {
+    struct AppServices {
+        users: UserService = auto,
+        primary: Arc<Config> = Arc::new(Config::builder().build()?),
+        fallback: Arc<Config> = Arc::new(Config::builder().build()?),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use service_builder::{builder, services};
+
+#[builder]
+struct Config {
+    port: u16,
+}
+
+#[builder]
+struct Server {
+    config: Config,
+}
+
+services! {
+    struct AppServices {
+        server: Server = auto,
+        config: Config = Config::builder().port(server.port()).build()?,
+    }
+}
+
+impl Server {
+    fn port(&self) -> u16 {
+        8080
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use service_builder::{builder, services};
+use std::sync::Arc;
+
+#[builder]
+struct UserService {
+    posts: Arc<PostService>,
+}
+
+#[builder]
+struct PostService {
+    users: Arc<UserService>,
+}
+
+services! {
+    struct AppServices {
+        users: Arc<UserService> = Arc::new(UserService::builder().posts(posts.clone()).build()?),
+        posts: Arc<PostService> = Arc::new(PostService::builder().users(users.clone()).build()?),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use service_builder::{builder, services};
+use std::sync::Arc;
+
+trait UserRepository {}
+
+#[builder]
+struct UserService {
+    repository: Arc<dyn UserRepository>,
+}
+
+services! {
+    struct AppServices {
+        users: UserService = auto,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use service_builder::{builder, services};
+
+#[builder]
+struct Config {
+    port: u16,
+}
+
+#[builder]
+struct Server {
+    config: Config,
+}
+
+services! {
+    struct AppServices {
+        server: Server = auto(config: settings),
+        config: Config = Config::builder().port(8080).build()?,
+    }
+}
+
+services! {
+    struct OtherServices {
+        server: Server = auto(settings: config),
+        config: Config = Config::builder().port(8080).build()?,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use service_builder::builder;
+
+#[builder]
+struct PoolConfig {
+    #[builder(getter, defualt = "10")]
+    max_size: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use service_builder::builder;
+
+trait Cache {}
+
+#[builder]
+struct UserService {
+    #[builder(inject(qualifer = "local"))]
+    cache: std::sync::Arc<dyn Cache>,
+}
+
+fn main() {}
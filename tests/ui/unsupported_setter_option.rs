@@ -0,0 +1,13 @@
+use service_builder::builder;
+
+fn positive(value: &u32) -> Result<(), String> {
+    if *value == 0 { Err("must be positive".to_string()) } else { Ok(()) }
+}
+
+#[builder]
+struct PoolConfig {
+    #[builder(setter(chek = "positive"), getter)]
+    max_size: u32,
+}
+
+fn main() {}
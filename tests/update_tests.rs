@@ -0,0 +1,223 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use std::sync::{Arc, Mutex};
+
+#[builder(update, invariant = "PoolConfig::check_bounds")]
+struct PoolConfig {
+    #[builder(getter)]
+    min_size: u32,
+    #[builder(getter, observe)]
+    max_size: u32,
+    #[builder(factory = "Ok::<_, String>(format!(\"{}..{}\", min_size, max_size))")]
+    range: String,
+    #[builder(skip)]
+    resizes: u32,
+}
+
+impl PoolConfig {
+    fn check_bounds(&self) -> Result<(), String> {
+        if self.min_size > self.max_size {
+            Err(format!("min_size {} exceeds max_size {}", self.min_size, self.max_size))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[builder(update)]
+struct Endpoint {
+    #[builder(getter)]
+    host: String,
+    #[builder(getter, default = "443")]
+    port: u16,
+}
+
+fn pool() -> PoolConfig {
+    PoolConfig::builder().min_size(2).max_size(10).build().unwrap()
+}
+
+#[test]
+fn test_update_changes_related_fields_together() {
+    let mut config = pool();
+
+    // Raising min_size first on its own would break the invariant
+    config.update(|builder| builder.min_size(20).max_size(40)).unwrap();
+
+    assert_eq!(*config.get_min_size(), 20);
+    assert_eq!(*config.get_max_size(), 40);
+    assert_eq!(config.range, "20..40");
+}
+
+#[test]
+fn test_failed_update_leaves_struct_unchanged() {
+    let mut config = pool();
+    config.resizes = 3;
+
+    match config.update(|builder| builder.min_size(20)).map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "PoolConfig: min_size 20 exceeds max_size 10"),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+
+    assert_eq!(*config.get_min_size(), 2);
+    assert_eq!(*config.get_max_size(), 10);
+    assert_eq!(config.range, "2..10");
+    assert_eq!(config.resizes, 3);
+}
+
+#[test]
+fn test_update_keeps_skipped_fields_and_listeners() {
+    let mut config = pool();
+    config.resizes = 3;
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&changes);
+    config.subscribe_max_size(move |old: &u32, new: &u32| recorded.lock().unwrap().push((*old, *new)));
+
+    config.update(|builder| builder.max_size(16)).unwrap();
+    config.set_max_size(32).unwrap();
+
+    assert_eq!(config.resizes, 3);
+    assert_eq!(*changes.lock().unwrap(), vec![(10, 16), (16, 32)]);
+}
+
+#[test]
+fn test_untouched_fields_keep_current_values() {
+    let mut endpoint = Endpoint::builder().host("api.example.com".to_string()).port(8443).build().unwrap();
+
+    endpoint.update(|builder| builder.host("api2.example.com".to_string())).unwrap();
+
+    assert_eq!(endpoint.get_host(), "api2.example.com");
+    assert_eq!(*endpoint.get_port(), 8443);
+}
+
+fn logged(message: String) -> String {
+    format!("log({})", message)
+}
+
+fn positive(value: &u32) -> Result<(), String> {
+    if *value == 0 {
+        Err("must be positive".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[builder(update)]
+struct Greeter {
+    #[builder(getter, decorate = "logged")]
+    message: String,
+    #[builder(getter, setter(check = "positive"))]
+    repeat: u32,
+    #[builder(getter)]
+    capacity: u32,
+    #[builder(factory = "Ok::<_, String>(capacity * 2)", overridable)]
+    buffer: u32,
+}
+
+fn greeter() -> Greeter {
+    Greeter::builder()
+        .message("hello".to_string())
+        .repeat(1)
+        .capacity(8)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_update_decorates_only_new_values() {
+    let mut greeter = greeter();
+    assert_eq!(greeter.get_message(), "log(hello)");
+
+    greeter.update(|builder| builder.repeat(2)).unwrap();
+    assert_eq!(greeter.get_message(), "log(hello)");
+
+    greeter.update(|builder| builder.message("hi".to_string())).unwrap();
+    assert_eq!(greeter.get_message(), "log(hi)");
+}
+
+#[test]
+fn test_update_recomputes_overridable_factory() {
+    let mut greeter = greeter();
+    assert_eq!(greeter.buffer, 16);
+
+    greeter.update(|builder| builder.capacity(32)).unwrap();
+    assert_eq!(greeter.buffer, 64);
+
+    greeter.update(|builder| builder.buffer(10)).unwrap();
+    assert_eq!(greeter.buffer, 10);
+}
+
+#[test]
+fn test_update_runs_field_checks() {
+    let mut greeter = greeter();
+
+    match greeter.update(|builder| builder.repeat(0)).map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "Greeter.repeat: must be positive"),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    assert_eq!(*greeter.get_repeat(), 1);
+}
+
+#[test]
+fn test_update_notifies_only_changed_fields() {
+    let mut config = pool();
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&changes);
+    config.subscribe_max_size(move |old: &u32, new: &u32| recorded.lock().unwrap().push((*old, *new)));
+
+    config.update(|builder| builder.min_size(4)).unwrap();
+    assert!(changes.lock().unwrap().is_empty());
+
+    config.update(|builder| builder.max_size(10)).unwrap();
+    assert_eq!(*changes.lock().unwrap(), vec![(10, 10)]);
+}
+
+#[builder(update, invariant = "Quota::check_limit")]
+struct Quota {
+    #[builder(getter)]
+    used: u32,
+    #[builder(skip)]
+    limit: u32,
+}
+
+impl Quota {
+    fn check_limit(&self) -> Result<(), String> {
+        if self.used > self.limit {
+            Err(format!("{} > {}", self.used, self.limit))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn test_invariant_sees_skipped_fields() {
+    // The skipped limit defaults to 0, so the quota is built unused and raised afterwards
+    let mut quota = Quota::builder().used(0).build().unwrap();
+    quota.limit = 100;
+
+    quota.update(|builder| builder.used(5)).unwrap();
+    assert_eq!(*quota.get_used(), 5);
+    assert_eq!(quota.limit, 100);
+
+    match quota.update(|builder| builder.used(150)).map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "Quota: 150 > 100"),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    assert_eq!(*quota.get_used(), 5);
+    assert_eq!(quota.limit, 100);
+}
+
+// A struct named like a generated type parameter
+#[builder(update)]
+struct F {
+    #[builder(getter)]
+    level: u8,
+}
+
+#[test]
+fn test_update_on_struct_named_f() {
+    let mut f = F::builder().level(1).build().unwrap();
+    f.update(|builder| builder.level(2)).unwrap();
+    assert_eq!(*f.get_level(), 2);
+}
@@ -0,0 +1,123 @@
+use service_builder::builder;
+use service_builder::error::BuildError;
+use std::sync::{Arc, Mutex};
+
+fn positive(value: &u32) -> Result<(), String> {
+    if *value == 0 {
+        Err("must be positive".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[builder(invariant = "PoolConfig::check_bounds")]
+struct PoolConfig {
+    #[builder(getter, setter(check = "positive"))]
+    min_size: u32,
+    #[builder(getter, setter(check = "positive"), observe)]
+    max_size: u32,
+    #[builder(getter, setter)]
+    name: String,
+}
+
+impl PoolConfig {
+    fn check_bounds(&self) -> Result<(), String> {
+        if self.min_size > self.max_size {
+            Err(format!("min_size {} exceeds max_size {}", self.min_size, self.max_size))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[builder]
+struct RetryPolicy {
+    #[builder(getter, setter(check = "positive"))]
+    attempts: u32,
+    #[builder(getter, setter)]
+    backoff_ms: u64,
+}
+
+fn pool() -> PoolConfig {
+    PoolConfig::builder()
+        .min_size(2)
+        .max_size(10)
+        .name("primary".to_string())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_check_rejects_value() {
+    let mut policy = RetryPolicy::builder().attempts(3).backoff_ms(100).build().unwrap();
+
+    match policy.set_attempts(0).map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "RetryPolicy.attempts: must be positive"),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    assert_eq!(*policy.get_attempts(), 3);
+
+    policy.set_attempts(5).unwrap();
+    assert_eq!(*policy.get_attempts(), 5);
+
+    // Setters without a check are unchanged
+    policy.set_backoff_ms(250);
+    assert_eq!(*policy.get_backoff_ms(), 250);
+}
+
+#[test]
+fn test_check_runs_on_build() {
+    let result = RetryPolicy::builder().attempts(0).backoff_ms(100).build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "RetryPolicy.attempts: must be positive"),
+        Err(other) => panic!("expected a configuration error, got {:?}", other),
+        Ok(policy) => panic!("expected an error, built {} attempts", policy.get_attempts()),
+    }
+    assert!(RetryPolicy::builder().attempts(0).backoff_ms(100).build_with_defaults().is_err());
+}
+
+#[test]
+fn test_invariant_violation_rolls_back() {
+    let mut config = pool();
+
+    match config.set_min_size(20) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "PoolConfig: min_size 20 exceeds max_size 10"),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    assert_eq!(*config.get_min_size(), 2);
+
+    // Setters without a check still enforce the invariant
+    config.set_name("replica".to_string()).unwrap();
+    assert_eq!(config.get_name(), "replica");
+}
+
+#[test]
+fn test_invariant_checked_on_build() {
+    let result = PoolConfig::builder()
+        .min_size(20)
+        .max_size(10)
+        .name("primary".to_string())
+        .build();
+
+    match result.map_err(BuildError::into_inner) {
+        Err(BuildError::ConfigurationError(msg)) => assert_eq!(msg, "PoolConfig: min_size 20 exceeds max_size 10"),
+        Err(other) => panic!("expected a configuration error, got {:?}", other),
+        Ok(config) => panic!("expected an error, built {}", config.get_name()),
+    }
+}
+
+#[test]
+fn test_observers_only_see_accepted_values() {
+    let mut config = pool();
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&changes);
+    config.subscribe_max_size(move |old: &u32, new: &u32| recorded.lock().unwrap().push((*old, *new)));
+
+    assert!(config.set_max_size(0).is_err());
+    assert!(config.set_max_size(1).is_err());
+    config.set_max_size(16).unwrap();
+
+    assert_eq!(*config.get_max_size(), 16);
+    assert_eq!(*changes.lock().unwrap(), vec![(10, 16)]);
+}
@@ -0,0 +1,104 @@
+use service_builder::builder;
+use std::sync::{Arc, Weak};
+
+#[builder]
+struct Worker {
+    id: u32,
+    #[builder(weak, setter)]
+    manager: Weak<Manager>,
+}
+
+#[builder]
+struct Manager {
+    name: String,
+    worker: Arc<Worker>,
+    #[builder(weak)]
+    this: Weak<Self>,
+}
+
+impl Manager {
+    fn describe_worker(&self) -> String {
+        let manager = self.get_this().expect("manager is alive");
+        format!("worker {} of {}", self.worker.id, manager.name)
+    }
+}
+
+fn worker(id: u32) -> Arc<Worker> {
+    Arc::new(Worker::builder().id(id).build().unwrap())
+}
+
+#[test]
+fn test_build_cyclic_sets_weak_self() {
+    let manager = Manager::builder()
+        .name("scheduler".to_string())
+        .worker(worker(1))
+        .build_cyclic()
+        .unwrap();
+
+    assert!(Arc::ptr_eq(&manager, &manager.get_this().unwrap()));
+    assert_eq!(manager.describe_worker(), "worker 1 of scheduler");
+    assert!(manager.worker.get_manager().is_none());
+}
+
+#[test]
+fn test_build_cyclic_with_wires_sibling() {
+    let manager = Manager::builder()
+        .name("scheduler".to_string())
+        .worker(worker(7))
+        .build_cyclic_with(|manager, this| {
+            Arc::get_mut(&mut manager.worker).unwrap().set_manager(this.clone());
+        })
+        .unwrap();
+
+    let worker = manager.worker.clone();
+    assert_eq!(worker.get_manager().unwrap().name, "scheduler");
+
+    // Weak references do not keep the manager alive
+    drop(manager);
+    assert!(worker.get_manager().is_none());
+}
+
+#[test]
+fn test_weak_fields_default_to_empty() {
+    let manager = Manager::builder()
+        .name("scheduler".to_string())
+        .worker(worker(1))
+        .build()
+        .unwrap();
+
+    assert!(manager.get_this().is_none());
+}
+
+#[test]
+fn test_weak_field_set_on_builder() {
+    let manager = Manager::builder()
+        .name("scheduler".to_string())
+        .worker(worker(1))
+        .build_cyclic()
+        .unwrap();
+
+    let worker = Worker::builder()
+        .id(2)
+        .manager(Arc::downgrade(&manager))
+        .build()
+        .unwrap();
+
+    assert!(Arc::ptr_eq(&worker.get_manager().unwrap(), &manager));
+    assert_eq!(worker.id, 2);
+}
+
+// A struct named like a generated type parameter
+#[builder]
+struct F {
+    #[builder(weak)]
+    this: Weak<Self>,
+}
+
+#[test]
+fn test_build_cyclic_on_struct_named_f() {
+    let mut wired = false;
+    let f = F::builder().build_cyclic_with(|_, _| wired = true).unwrap();
+
+    assert!(wired);
+    assert!(Arc::ptr_eq(&f, &f.get_this().unwrap()));
+}